@@ -14,11 +14,24 @@ fn main() {
     let mut file = File::create(&path).unwrap();
 
     Entities::new()
-        .with_component("body"     , "bodies"    , "Body"     )
-        .with_component("broadcast", "broadcasts", "Broadcast")
-        .with_component("maneuver" , "maneuvers" , "Maneuver" )
-        .with_component("planet"   , "planets"   , "Planet"   )
-        .with_component("ship"     , "ships"     , "Ship"     )
+        .with_component("body"     , "bodies"     , "Body"     )
+        .with_component("broadcast", "broadcasts" , "Broadcast")
+        .with_component("cargo"    , "cargo_holds", "Cargo"    )
+        .with_component("container", "containers" , "Container")
+        .with_component("docking"  , "dockings"   , "Docking"  )
+        .with_component("drone_bay", "drone_bays" , "DroneBay" )
+        .with_component("ftl_drive", "ftl_drives" , "FtlDrive" )
+        .with_component("hull"     , "hulls"      , "Hull"     )
+        .with_component("launcher" , "launchers"  , "Launcher" )
+        .with_component("maneuver" , "maneuvers"  , "Maneuver" )
+        .with_component("missile"  , "missiles"   , "Missile"  )
+        .with_component("planet"   , "planets"    , "Planet"   )
+        .with_component("probe"    , "probes"     , "Probe"    )
+        .with_component("sensors"  , "sensors"    , "Sensors"  )
+        .with_component("ship"     , "ships"      , "Ship"     )
+        .with_component("signature", "signatures" , "Signature")
+        .with_component("station"  , "stations"   , "Station"  )
+        .with_component("wreck"    , "wrecks"     , "Wreck"    )
         .generate(&mut file)
         .unwrap();
 }
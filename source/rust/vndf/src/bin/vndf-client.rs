@@ -23,8 +23,24 @@ use vndf::client::network::Network;
 use vndf::client::times::Times;
 use vndf::shared::protocol::client::{
     cancel_maneuver,
+    collect_cargo,
+    dispatch_drones,
+    dock,
+    eject_cargo,
     ftl_jump,
+    launch_missile,
+    login,
+    recall_drones,
+    respawn,
+    refuel,
+    repair,
+    salvage_wreck,
     schedule_maneuver,
+    set_launcher_mode,
+    set_radar,
+    set_sensor_focus,
+    undock,
+    update_maneuver,
 };
 use vndf::shared::protocol::client::Event as ClientEvent;
 use vndf::shared::protocol::client::event as client_event;
@@ -62,13 +78,17 @@ fn run<I: Interface>(args: Args, mut interface: I) {
 
     let mut frame        = Frame::new();
     let mut interpolator = Interpolator::new();
+    let mut containers   = Interpolator::new();
+    let mut missiles     = Interpolator::new();
+    let mut wrecks       = Interpolator::new();
+    let mut stations     = Interpolator::new();
 
     let mut network = Network::new(args.server);
     let mut last_server_activity = times.client_now_s();
 
     let mut frame_time = times.client_now_s();
 
-    network.send(ClientEvent::Public(client_event::Public::Login));
+    network.send(login(args.design.clone(), args.spawner.clone()));
 
     'main: loop {
         let now = times.client_now_s();
@@ -86,6 +106,14 @@ fn run<I: Interface>(args: Args, mut interface: I) {
 
         for event in input_events {
             match event {
+                InputEvent::Respawn => {
+                    network.send(respawn());
+
+                    frame.message = Message::Notice(
+                        "Requesting a new ship".to_string()
+                    );
+                },
+
                 InputEvent::StartBroadcast(message) =>
                     if message.len() == 0 {
                         frame.message = Message::Error(
@@ -121,6 +149,14 @@ fn run<I: Interface>(args: Args, mut interface: I) {
                             );
                 },
 
+                InputEvent::UpdateManeuver(id, data) => {
+                    network.send(update_maneuver(id, data));
+
+                    frame.message = Message::Notice(
+                        "Updating maneuver".to_string()
+                    );
+                },
+
                 InputEvent::CancelManeuver(id) => {
                     network.send(cancel_maneuver(id));
 
@@ -131,6 +167,99 @@ fn run<I: Interface>(args: Args, mut interface: I) {
 
                 InputEvent::FtlJump(destination_time_s) => {
                     network.send(ftl_jump(destination_time_s));
+
+                    frame.message = Message::Notice(
+                        "Initiating FTL jump".to_string()
+                    );
+                },
+
+                InputEvent::EjectCargo(index) => {
+                    network.send(eject_cargo(index));
+
+                    frame.message = Message::Notice(
+                        "Ejecting cargo".to_string()
+                    );
+                },
+
+                InputEvent::CollectCargo(container_id) => {
+                    network.send(collect_cargo(container_id));
+
+                    frame.message = Message::Notice(
+                        "Collecting cargo".to_string()
+                    );
+                },
+
+                InputEvent::SalvageWreck(wreck_id) => {
+                    network.send(salvage_wreck(wreck_id));
+
+                    frame.message = Message::Notice(
+                        "Salvaging wreck".to_string()
+                    );
+                },
+
+                InputEvent::DispatchDrones(wreck_id) => {
+                    network.send(dispatch_drones(wreck_id));
+
+                    frame.message = Message::Notice(
+                        "Dispatching drones".to_string()
+                    );
+                },
+
+                InputEvent::RecallDrones => {
+                    network.send(recall_drones());
+
+                    frame.message = Message::Notice(
+                        "Recalling drones".to_string()
+                    );
+                },
+
+                InputEvent::Dock(station_id) => {
+                    network.send(dock(station_id));
+
+                    frame.message = Message::Notice(
+                        "Docking".to_string()
+                    );
+                },
+
+                InputEvent::Undock => {
+                    network.send(undock());
+
+                    frame.message = Message::Notice(
+                        "Undocking".to_string()
+                    );
+                },
+
+                InputEvent::Refuel => {
+                    network.send(refuel());
+
+                    frame.message = Message::Notice(
+                        "Refuelling".to_string()
+                    );
+                },
+
+                InputEvent::Repair => {
+                    network.send(repair());
+
+                    frame.message = Message::Notice(
+                        "Repairing".to_string()
+                    );
+                },
+
+                InputEvent::LaunchMissile(target_id) => {
+                    network.send(launch_missile(target_id));
+
+                    frame.message = Message::Notice(
+                        "Launching missile".to_string()
+                    );
+                },
+                InputEvent::SetLauncherMode(mode) => {
+                    network.send(set_launcher_mode(mode));
+                },
+                InputEvent::SetRadar(active) => {
+                    network.send(set_radar(active));
+                },
+                InputEvent::SetSensorFocus(focus) => {
+                    network.send(set_sensor_focus(focus));
                 },
 
                 InputEvent::Track(track) => {
@@ -179,6 +308,10 @@ fn run<I: Interface>(args: Args, mut interface: I) {
                     frame.ship_id = Some(ship_id);
                 },
                 server::Event::UpdateEntity(entity) => {
+                    // The entity might have been a contact before it was
+                    // identified.
+                    frame.contacts.remove(&entity.id);
+
                     if let Some(body) = entity.body {
                         if let Some(_) = entity.ship {
                             interpolator.update_ship(
@@ -195,6 +328,10 @@ fn run<I: Interface>(args: Args, mut interface: I) {
                         }
                     }
 
+                    if let Some(hull) = entity.hull {
+                        frame.hulls.insert(entity.id, hull);
+                    }
+
                     if let Some(planet) = entity.planet {
                         frame.planets.insert(entity.id, planet);
 
@@ -209,6 +346,61 @@ fn run<I: Interface>(args: Args, mut interface: I) {
                         frame.maneuvers.insert(entity.id, maneuver.data);
                     }
 
+                    if Some(entity.id) == frame.ship_id {
+                        frame.ship      = entity.ship;
+                        frame.cargo     = entity.cargo;
+                        frame.docking   = entity.docking;
+                        frame.drone_bay = entity.drone_bay;
+                        frame.launcher  = entity.launcher;
+                        frame.sensors   = entity.sensors;
+                    }
+
+                    if let Some(_) = entity.missile {
+                        if let Some(body) = entity.body {
+                            missiles.update_ship(
+                                times.server_last_known_s(),
+                                entity.id,
+                                body,
+                            );
+                        }
+                    }
+
+                    if let Some(container) = entity.container {
+                        if let Some(body) = entity.body {
+                            containers.update_ship(
+                                times.server_last_known_s(),
+                                entity.id,
+                                body,
+                            );
+                        }
+
+                        frame.contents.insert(entity.id, container);
+                    }
+
+                    if let Some(wreck) = entity.wreck {
+                        if let Some(body) = entity.body {
+                            wrecks.update_ship(
+                                times.server_last_known_s(),
+                                entity.id,
+                                body,
+                            );
+                        }
+
+                        frame.wreck_contents.insert(entity.id, wreck);
+                    }
+
+                    if let Some(station) = entity.station {
+                        if let Some(body) = entity.body {
+                            stations.update_ship(
+                                times.server_last_known_s(),
+                                entity.id,
+                                body,
+                            );
+                        }
+
+                        frame.station_names.insert(entity.id, station.name);
+                    }
+
                     match entity.broadcast {
                         Some(broadcast) => {
                             frame.broadcasts.insert(entity.id, broadcast.message);
@@ -221,8 +413,51 @@ fn run<I: Interface>(args: Args, mut interface: I) {
                 server::Event::RemoveEntity(id) => {
                     frame.broadcasts.remove(&id);
                     frame.maneuvers.remove(&id);
+                    frame.contents.remove(&id);
+                    frame.hulls.remove(&id);
+                    frame.contacts.remove(&id);
+                    frame.wreck_contents.remove(&id);
+                    frame.station_names.remove(&id);
 
                     interpolator.remove_ship(&id);
+                    containers.remove_ship(&id);
+                    missiles.remove_ship(&id);
+                    wrecks.remove_ship(&id);
+                    stations.remove_ship(&id);
+                },
+                server::Event::UpdateContact(id, contact) => {
+                    frame.contacts.insert(id, contact);
+                },
+                server::Event::Rejected(reason) => {
+                    frame.message = Message::Error(reason);
+                },
+                server::Event::FtlJumpAborted(reason) => {
+                    frame.message = Message::Error(reason);
+                },
+                server::Event::Collided(other_id, damage) => {
+                    frame.message = Message::Error(format!(
+                        "Collided with {} ({:.1} damage)",
+                        other_id, damage,
+                    ));
+                },
+                server::Event::ShipDestroyed(source) => {
+                    frame.message = Message::Error(format!(
+                        "Your ship was destroyed by {}. Type `respawn` to get \
+                        a new one.",
+                        source,
+                    ));
+
+                    // Everything we know about our own ship is stale now.
+                    frame.ship_id   = None;
+                    frame.ship      = None;
+                    frame.cargo     = None;
+                    frame.docking   = None;
+                    frame.drone_bay = None;
+                    frame.launcher  = None;
+                    frame.sensors   = None;
+
+                    frame.maneuvers.clear();
+                    frame.select_ids.clear();
                 },
             }
 
@@ -235,6 +470,30 @@ fn run<I: Interface>(args: Args, mut interface: I) {
             &mut frame.ships,
         );
 
+        frame.containers.clear();
+        containers.interpolate(
+            times.server_interpolated_s(),
+            &mut frame.containers,
+        );
+
+        frame.missiles.clear();
+        missiles.interpolate(
+            times.server_interpolated_s(),
+            &mut frame.missiles,
+        );
+
+        frame.wrecks.clear();
+        wrecks.interpolate(
+            times.server_interpolated_s(),
+            &mut frame.wrecks,
+        );
+
+        frame.stations.clear();
+        stations.interpolate(
+            times.server_interpolated_s(),
+            &mut frame.stations,
+        );
+
         if now - last_server_activity > args.net_timeout_s {
             frame.message = Message::Error(
                 "Lost connection to server".to_string()
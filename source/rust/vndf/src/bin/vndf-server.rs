@@ -87,36 +87,25 @@ fn main() {
             );
         }
 
-        for entity in game_state.export_entities() {
-            let recipients = match entity.maneuver {
-                Some(maneuver) => {
-                    // Not pretty, but works.
-                    let mut address = None;
-                    for (the_address, client) in &clients.clients {
-                        if client.ship_id == maneuver.ship_id {
-                            address = Some(the_address);
-                        }
-                    }
-
-                    match address {
-                        Some(address) => Recipients::One(*address),
-                        None => {
-                            warn!(
-                                "Found maneuver with no matching ship: {:?}",
-                                maneuver
-                            );
-                            continue;
-                        },
-                    }
-                },
-
-                None => Recipients::All,
-            };
+        for (ship_id, event) in game_state.notifications() {
+            match clients.address_of(ship_id) {
+                Some(address) =>
+                    outgoing_events.push(event, Recipients::One(address)),
+                None =>
+                    debug!("No client for notification: {:?}", event),
+            }
+        }
 
-            outgoing_events.push(
-                ServerEvent::UpdateEntity(entity),
-                recipients,
-            );
+        let entities: Vec<_> = game_state.export_entities().collect();
+        for entity in &entities {
+            // Every client only learns about what its own ship can detect.
+            for (&address, client) in &clients.clients {
+                let event = game_state.update_for(client.ship_id, entity);
+
+                if let Some(event) = event {
+                    outgoing_events.push(event, Recipients::One(address));
+                }
+            }
         }
 
         outgoing_events.send(&mut clients, &mut network);
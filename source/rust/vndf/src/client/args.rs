@@ -6,11 +6,15 @@ use std::net::{
 
 use getopts::Options;
 
+use shared::game::design::DEFAULT_DESIGN;
+
 
 pub struct Args {
 	pub headless      : bool,
 	pub server        : SocketAddr,
 	pub net_timeout_s : f64,
+	pub design        : String,
+	pub spawner       : Option<String>,
 }
 
 impl Args {
@@ -39,6 +43,18 @@ impl Args {
 			"network timeout in seconds",
 			"0.5"
 		);
+		options.optopt(
+			"",
+			"design",
+			"ship design to use",
+			DEFAULT_DESIGN,
+		);
+		options.optopt(
+			"",
+			"spawner",
+			"name of the spawner to enter the game at",
+			"default",
+		);
 		options.optopt(
 			"",
 			"sleep-duration",
@@ -64,6 +80,11 @@ impl Args {
 			None            => 5.0,
 		};
 
+		let design = match matches.opt_str("design") {
+			Some(design) => design,
+			None         => DEFAULT_DESIGN.to_string(),
+		};
+
 		let server_address = (host.as_ref(), port);
 		let server_address = match server_address.to_socket_addrs() {
 			Ok(mut addresses) => match addresses.next() {
@@ -83,6 +104,8 @@ impl Args {
 			headless      : matches.opt_present("headless"),
 			server        : server_address,
 			net_timeout_s : net_timeout_s,
+			design        : design,
+			spawner       : matches.opt_str("spawner"),
 		}
 	}
 }
@@ -7,6 +7,7 @@ use glutin::Event::{
     KeyboardInput,
     ReceivedCharacter,
 };
+use nalgebra::Norm;
 
 use client::console::Console;
 use client::interface::{
@@ -14,7 +15,23 @@ use client::interface::{
     InputEvent,
     Message,
 };
-use shared::game::data::{ManeuverData,EntityId};
+use shared::game::data::{
+    Body,
+    EntityId,
+    LauncherMode,
+    ManeuverData,
+    Orientation,
+    SensorFocus,
+};
+use shared::game::navigation::{
+    self,
+    Apsis,
+    Burn,
+    Orbit,
+    PREDICTION_STEP_S,
+    Target,
+};
+use shared::util::angle_of;
 
 pub struct Controller {
     pub console: Console,
@@ -28,6 +45,8 @@ pub struct Controller {
 
     comm_cache: HashMap<EntityId, String>, // cache of all broadcasts, to be compared
     comm_subscribe: bool,
+
+    plan: Vec<ManeuverData>, // maneuvers of the last plan, not scheduled yet
 }
 
 impl Controller {
@@ -49,6 +68,8 @@ impl Controller {
 
             comm_cache: HashMap::new(),
             comm_subscribe: true,
+
+            plan: Vec::new(),
         }
     }
 
@@ -243,6 +264,9 @@ impl Controller {
                     self.console.output.push(format!("{}: {}", eid, msg));
                 }
             },
+            "respawn" => {
+                events.push(InputEvent::Respawn);
+            },
             "start-broadcast" => {
                 events.push(InputEvent::StartBroadcast(args.to_string()));
             },
@@ -259,18 +283,21 @@ impl Controller {
             },
 
             "nav-data" => {
-                match frame.ship_id {
-                    Some(ship_id) => {
-                        let ship = frame.ships[&ship_id];
+                // The ship might not have arrived yet, even if its id has.
+                let ship = frame.ship_id.and_then(|id| frame.ships.get(&id));
 
+                match ship {
+                    Some(&ship) => {
                         self.console.output.push(format!(
                             "Position: ({}, {}); Velocity: ({}, {})\n",
                             ship.position.x, ship.position.y,
                             ship.velocity.x, ship.velocity.y,
                             ));
+
+                        self.show_orbit(&ship, frame);
                     },
                     None => {
-                        self.console.output.push(format!("No data available."));
+                        self.console.output.push(format!("No ship"));
                     },
                 }
             },
@@ -312,10 +339,11 @@ impl Controller {
                         // imprecise, we could count the time since the last
                         // frame arrived.
                         let data = ManeuverData {
-                            start_s   : game_time_s + delay_s,
-                            duration_s: duration_s,
-                            angle     : direction_rad,
-                            thrust    : thrust,
+                            start_s    : game_time_s + delay_s,
+                            duration_s : duration_s,
+                            angle      : direction_rad,
+                            thrust     : thrust,
+                            orientation: Orientation::Fixed,
                         };
 
                         events.push(
@@ -326,19 +354,108 @@ impl Controller {
                         self.console.output.push(format!("Error parsing arguments")),
                 }
             },
+            "schedule-burn" => {
+                let result = scan_fmt!(
+                    args,
+                    "{} {} {} {} {}",
+                    f64, f64, String, f64, EntityId
+                );
+
+                let (delay_s, duration_s, orientation, thrust) = match result {
+                    (Some(delay_s), Some(duration_s), Some(mode), Some(thrust), reference) => {
+                        match Controller::parse_orientation(&mode, reference) {
+                            Some(orientation) =>
+                                (delay_s, duration_s, orientation, thrust),
+                            None => {
+                                self.console.output.push(format!("Invalid orientation: {}", mode));
+                                return;
+                            },
+                        }
+                    },
+                    _ => {
+                        self.console.output.push(format!("Error parsing arguments"));
+                        return;
+                    },
+                };
+
+                let game_time_s = match frame.game_time_s {
+                    Some(game_time_s) => game_time_s,
+                    None => {
+                        self.console.output.push(format!(
+                            "{} {}",
+                            "Server connection not fully established ",
+                            "yet. Please try again in a moment."
+                        ));
+                        return;
+                    },
+                };
+
+                let data = ManeuverData {
+                    start_s    : game_time_s + delay_s,
+                    duration_s : duration_s,
+                    angle      : 0.0,
+                    thrust     : thrust,
+                    orientation: orientation,
+                };
+
+                events.push(InputEvent::ScheduleManeuver(data));
+            },
             "list-maneuvers" => {
                 self.console.output.push(format!("Scheduled maneuvers:"));
                 for (id, maneuver) in &frame.maneuvers {
                     self.console.output.push(format!(
-                        "{}: Start: {}; Duration: {}; Angle: {}, Thrust: {}",
+                        "{}: Start: {}; Duration: {}; Angle: {}, Thrust: {}, Orientation: {:?}",
                         id,
                         maneuver.start_s,
                         maneuver.duration_s,
                         maneuver.angle,
                         maneuver.thrust,
+                        maneuver.orientation,
                     ));
                 }
             },
+            "update-maneuver" => {
+                let result = scan_fmt!(
+                    args,
+                    "{} {} {} {} {}",
+                    EntityId, f64, f64, f64, f64
+                );
+
+                match result {
+                    (Some(id), Some(delay_s), Some(duration_s), Some(direction_deg), Some(thrust)) => {
+                        let game_time_s = match frame.game_time_s {
+                            Some(game_time_s) => game_time_s,
+                            None => {
+                                self.console.output.push(format!(
+                                    "{} {}",
+                                    "Server connection not fully established ",
+                                    "yet. Please try again in a moment."
+                                ));
+                                return;
+                            },
+                        };
+
+                        // Keep the maneuver's orientation. Only the parameters
+                        // that can be entered here are changed.
+                        let mut data = match frame.maneuvers.get(&id) {
+                            Some(data) => *data,
+                            None => {
+                                self.console.output.push(format!("Unknown maneuver: {}", id));
+                                return;
+                            },
+                        };
+
+                        data.start_s    = game_time_s + delay_s;
+                        data.duration_s = duration_s;
+                        data.angle      = direction_deg.to_radians();
+                        data.thrust     = thrust;
+
+                        events.push(InputEvent::UpdateManeuver(id, data));
+                    },
+                    _ =>
+                        self.console.output.push(format!("Error parsing arguments")),
+                }
+            },
             "cancel-maneuver" => {
                 let result = scan_fmt!(
                     args,
@@ -355,6 +472,215 @@ impl Controller {
                 }
             },
 
+            "plan-rendezvous" => {
+                self.plan.clear();
+
+                let result = scan_fmt!(
+                    args,
+                    "{} {} {}",
+                    EntityId, f64, f64
+                );
+
+                let (target_id, delay_s, transfer_s) = match result {
+                    (Some(target_id), Some(delay_s), Some(transfer_s)) =>
+                        (target_id, delay_s, transfer_s),
+                    _ => {
+                        self.console.output.push(format!("Error parsing arguments"));
+                        return;
+                    },
+                };
+
+                let valid = self.check_prediction_span(delay_s)
+                    && self.check_prediction_span(transfer_s)
+                    && self.check_prediction_span(delay_s + transfer_s);
+                if !valid {
+                    return;
+                }
+
+                let (ship, max_thrust, now_s) = match Controller::own_ship(frame) {
+                    Some(own_ship) => own_ship,
+                    None => {
+                        self.console.output.push(format!("No data available."));
+                        return;
+                    },
+                };
+
+                let target = match frame.find_body(target_id) {
+                    Some(&target) => target,
+                    None => {
+                        self.console.output.push(format!("Unknown target: {}", target_id));
+                        return;
+                    },
+                };
+
+                let planets: Vec<_> = frame.planets.values().cloned().collect();
+                let departure_s     = now_s + delay_s;
+
+                let burns = navigation::plan_rendezvous(
+                    &ship,
+                    &target,
+                    &planets,
+                    now_s,
+                    departure_s,
+                    departure_s + transfer_s,
+                );
+
+                match burns {
+                    Some(burns) =>
+                        self.show_plan(&burns, ship.mass, max_thrust, now_s),
+                    None =>
+                        self.console.output.push(format!("No rendezvous found. Try a different delay or transfer time.")),
+                }
+            },
+            "plan-hohmann" => {
+                self.plan.clear();
+
+                let result = scan_fmt!(
+                    args,
+                    "{} {} {}",
+                    EntityId, f64, f64
+                );
+
+                let (body_id, radius, delay_s) = match result {
+                    (Some(body_id), Some(radius), Some(delay_s)) =>
+                        (body_id, radius, delay_s),
+                    _ => {
+                        self.console.output.push(format!("Error parsing arguments"));
+                        return;
+                    },
+                };
+
+                self.plan_hohmann(frame, body_id, radius, None, delay_s);
+            },
+            "plan-moon-transfer" => {
+                self.plan.clear();
+
+                let result = scan_fmt!(
+                    args,
+                    "{} {} {} {}",
+                    EntityId, EntityId, f64, f64
+                );
+
+                let (body_id, moon_id, altitude, delay_s) = match result {
+                    (Some(body_id), Some(moon_id), Some(altitude), Some(delay_s)) =>
+                        (body_id, moon_id, altitude, delay_s),
+                    _ => {
+                        self.console.output.push(format!("Error parsing arguments"));
+                        return;
+                    },
+                };
+
+                let (center, moon) =
+                    match (frame.planets.get(&body_id), frame.planets.get(&moon_id)) {
+                        (Some(center), Some(moon)) => (center, moon),
+                        _ => {
+                            self.console.output.push(format!("Unknown celestial"));
+                            return;
+                        },
+                    };
+
+                // Arrive on the near side of the moon, at the given altitude.
+                let to_moon = moon.position - center.position;
+                let radius  = to_moon.norm() - moon.radius - altitude;
+
+                self.plan_hohmann(
+                    frame,
+                    body_id,
+                    radius,
+                    Some(angle_of(to_moon)),
+                    delay_s,
+                );
+            },
+            "execute-plan" => {
+                if self.plan.is_empty() {
+                    self.console.output.push(format!("No plan to execute."));
+                    return;
+                }
+
+                for data in self.plan.drain(..) {
+                    events.push(InputEvent::ScheduleManeuver(data));
+                }
+            },
+
+            "closest-approach" => {
+                let result = scan_fmt!(
+                    args,
+                    "{} {}",
+                    EntityId, f64
+                );
+
+                let (target_id, duration_s) = match result {
+                    (Some(target_id), Some(duration_s)) =>
+                        (target_id, duration_s),
+                    _ => {
+                        self.console.output.push(format!("Error parsing arguments"));
+                        return;
+                    },
+                };
+
+                if !self.check_prediction_span(duration_s) {
+                    return;
+                }
+
+                let (ship, _, now_s) = match Controller::own_ship(frame) {
+                    Some(own_ship) => own_ship,
+                    None => {
+                        self.console.output.push(format!("No data available."));
+                        return;
+                    },
+                };
+
+                // Distances to planets are measured from their surface.
+                let (target, radius) = match frame.planets.get(&target_id) {
+                    Some(planet) =>
+                        (Target::Fixed(planet.position), planet.radius),
+                    None => match frame.find_body(target_id) {
+                        Some(&body) =>
+                            (Target::Body(body), 0.0),
+                        None => {
+                            self.console.output.push(format!("Unknown target: {}", target_id));
+                            return;
+                        },
+                    },
+                };
+
+                let planets: Vec<_> = frame.planets.values().cloned().collect();
+
+                let approach = navigation::closest_approach(
+                    &ship,
+                    target,
+                    &planets,
+                    now_s,
+                    duration_s,
+                    PREDICTION_STEP_S,
+                );
+
+                match approach {
+                    Some(approach) if approach.distance <= radius => {
+                        self.console.output.push(format!(
+                            "Warning: Impact on {} at {:.1} s (in {:.1} s)",
+                            target_id,
+                            approach.time_s,
+                            approach.time_s - now_s,
+                        ));
+                    },
+                    Some(approach) => {
+                        self.console.output.push(format!(
+                            "Closest approach: {:.1} m at {:.1} s (in {:.1} s)",
+                            approach.distance - radius,
+                            approach.time_s,
+                            approach.time_s - now_s,
+                        ));
+                        self.console.output.push(format!(
+                            "Type \"ftl-jump {:.1}\" to jump there.",
+                            approach.time_s - now_s,
+                        ));
+                    },
+                    None =>
+                        self.console.output.push(format!("No data available.")),
+                }
+            },
+
             "ftl-jump" => {
                 let result = scan_fmt!(
                     args,
@@ -383,6 +709,306 @@ impl Controller {
                         self.console.output.push(format!("Error parsing arguments")),
                 }
             },
+
+            "list-cargo" => {
+                match frame.cargo {
+                    Some(ref cargo) => {
+                        self.console.output.push(format!(
+                            "Cargo: {:.2} of {:.2}",
+                            cargo.mass(), cargo.capacity,
+                        ));
+                        for (i, item) in cargo.items.iter().enumerate() {
+                            self.console.output.push(format!(
+                                "{}: {:?} ({:.2})",
+                                i, item.kind, item.mass,
+                            ));
+                        }
+                    },
+                    None => {
+                        self.console.output.push(format!("No data available."));
+                    },
+                }
+            },
+            "eject-cargo" => {
+                let result = scan_fmt!(
+                    args,
+                    "{}",
+                    usize
+                );
+
+                match result {
+                    Some(index) => {
+                        events.push(InputEvent::EjectCargo(index));
+                    },
+                    _ =>
+                        self.console.output.push(format!("Error parsing arguments")),
+                }
+            },
+            "list-containers" => {
+                self.console.output.push(format!("Containers:"));
+                for (id, container) in &frame.contents {
+                    let body     = frame.containers.get(id);
+                    let distance = match (frame.own_ship(), body) {
+                        (Some(ship), Some(body)) => {
+                            let distance = body.position - ship.position;
+                            format!("{:.0}", distance.norm())
+                        },
+                        _ => format!("unknown"),
+                    };
+
+                    self.console.output.push(format!(
+                        "{}: Mass: {:.2}; Distance: {}",
+                        id, container.mass(), distance,
+                    ));
+                }
+            },
+            "collect-cargo" => {
+                let result = scan_fmt!(
+                    args,
+                    "{}",
+                    EntityId
+                );
+
+                match result {
+                    Some(id) => {
+                        events.push(InputEvent::CollectCargo(id));
+                    },
+                    _ =>
+                        self.console.output.push(format!("Error parsing arguments")),
+                }
+            },
+
+            "radar" => {
+                match args.trim() {
+                    "on"  => events.push(InputEvent::SetRadar(true)),
+                    "off" => events.push(InputEvent::SetRadar(false)),
+                    _ =>
+                        self.console.output.push(format!("Error parsing arguments")),
+                }
+            },
+            "sensor-focus" => {
+                if args.trim() == "off" {
+                    events.push(InputEvent::SetSensorFocus(None));
+                    return;
+                }
+
+                let result = scan_fmt!(
+                    args,
+                    "{} {}",
+                    f64, f64
+                );
+
+                match result {
+                    (Some(direction_deg), Some(width_deg)) => {
+                        let focus = SensorFocus {
+                            angle: direction_deg.to_radians(),
+                            width: width_deg.to_radians(),
+                        };
+
+                        events.push(InputEvent::SetSensorFocus(Some(focus)));
+                    },
+                    _ =>
+                        self.console.output.push(format!("Error parsing arguments")),
+                }
+            },
+            "sensor-data" => {
+                match frame.sensors {
+                    Some(sensors) => {
+                        self.console.output.push(format!(
+                            "Range: {} (radar: {}); Radar active: {}",
+                            sensors.range,
+                            sensors.radar_range,
+                            sensors.radar_on,
+                        ));
+
+                        if let Some(focus) = sensors.focus {
+                            self.console.output.push(format!(
+                                "Focus: {:.0} deg; Width: {:.0} deg",
+                                focus.angle.to_degrees(),
+                                focus.width.to_degrees(),
+                            ));
+                        }
+                    },
+                    None => {
+                        self.console.output.push(format!("No data available."));
+                    },
+                }
+            },
+
+            "list-wrecks" => {
+                self.console.output.push(format!("Wrecks:"));
+                for (id, wreck) in &frame.wreck_contents {
+                    let body     = frame.wrecks.get(id);
+                    let distance = match (frame.own_ship(), body) {
+                        (Some(ship), Some(body)) => {
+                            let distance = body.position - ship.position;
+                            format!("{:.0}", distance.norm())
+                        },
+                        _ => format!("unknown"),
+                    };
+
+                    self.console.output.push(format!(
+                        "{}: Salvage: {:.2}; Distance: {}",
+                        id, wreck.mass(), distance,
+                    ));
+                }
+            },
+            "salvage-wreck" => {
+                let result = scan_fmt!(
+                    args,
+                    "{}",
+                    EntityId
+                );
+
+                match result {
+                    Some(id) => {
+                        events.push(InputEvent::SalvageWreck(id));
+                    },
+                    _ =>
+                        self.console.output.push(format!("Error parsing arguments")),
+                }
+            },
+            "list-stations" => {
+                self.console.output.push(format!("Stations:"));
+                for (id, name) in &frame.station_names {
+                    let body     = frame.stations.get(id);
+                    let distance = match (frame.own_ship(), body) {
+                        (Some(ship), Some(body)) => {
+                            let distance = body.position - ship.position;
+                            format!("{:.0}", distance.norm())
+                        },
+                        _ => format!("unknown"),
+                    };
+
+                    let docked = frame.docking.map_or(false, |docking|
+                        docking.station_id == *id
+                    );
+
+                    self.console.output.push(format!(
+                        "{}: {}; Distance: {}{}",
+                        id, name, distance,
+                        if docked { " (docked)" } else { "" },
+                    ));
+                }
+            },
+            "dock" => {
+                let result = scan_fmt!(
+                    args,
+                    "{}",
+                    EntityId
+                );
+
+                match result {
+                    Some(id) => {
+                        events.push(InputEvent::Dock(id));
+                    },
+                    _ =>
+                        self.console.output.push(format!("Error parsing arguments")),
+                }
+            },
+            "undock" => {
+                events.push(InputEvent::Undock);
+            },
+            "refuel" => {
+                events.push(InputEvent::Refuel);
+            },
+            "repair" => {
+                events.push(InputEvent::Repair);
+            },
+            "dispatch-drones" => {
+                let result = scan_fmt!(
+                    args,
+                    "{}",
+                    EntityId
+                );
+
+                match result {
+                    Some(id) => {
+                        events.push(InputEvent::DispatchDrones(id));
+                    },
+                    _ =>
+                        self.console.output.push(format!("Error parsing arguments")),
+                }
+            },
+            "recall-drones" => {
+                events.push(InputEvent::RecallDrones);
+            },
+            "drone-status" => {
+                match frame.drone_bay {
+                    Some(drone_bay) => {
+                        self.console.output.push(format!(
+                            "Drones: {}", drone_bay.drones,
+                        ));
+
+                        match drone_bay.target {
+                            Some(wreck_id) => {
+                                let remaining = frame.wreck_contents
+                                    .get(&wreck_id)
+                                    .map_or(
+                                        format!("unknown"),
+                                        |wreck| format!("{:.2}", wreck.mass()),
+                                    );
+
+                                self.console.output.push(format!(
+                                    "Harvesting wreck {}; Salvaged: {:.2}; \
+                                    Remaining: {}",
+                                    wreck_id, drone_bay.salvaged, remaining,
+                                ));
+                            },
+                            None => {
+                                self.console.output.push(format!(
+                                    "Drones are docked. Last salvaged: {:.2}",
+                                    drone_bay.salvaged,
+                                ));
+                            },
+                        }
+                    },
+                    None => {
+                        self.console.output.push(format!("No drone bay."));
+                    },
+                }
+            },
+
+            "launch-missile" => {
+                let result = scan_fmt!(
+                    args,
+                    "{}",
+                    EntityId
+                );
+
+                match result {
+                    Some(id) => {
+                        events.push(InputEvent::LaunchMissile(id));
+                    },
+                    _ =>
+                        self.console.output.push(format!("Error parsing arguments")),
+                }
+            },
+            "launcher-mode" => {
+                match args.trim() {
+                    "offensive" => events.push(
+                        InputEvent::SetLauncherMode(LauncherMode::Offensive)
+                    ),
+                    "defensive" => events.push(
+                        InputEvent::SetLauncherMode(LauncherMode::Defensive)
+                    ),
+                    _ =>
+                        self.console.output.push(format!("Error parsing arguments")),
+                }
+            },
+            "weapons-data" => {
+                match frame.launcher {
+                    Some(launcher) => {
+                        self.console.output.push(format!(
+                            "Mode: {:?}; Missiles: {}; Reload time: {} s",
+                            launcher.mode, launcher.missiles, launcher.reload_s,
+                        ));
+                    },
+                    None => {
+                        self.console.output.push(format!("No data available."));
+                    },
+                }
+            },
             
             "select-entity" => {
                 let ents = Controller::parse_entity_ids(args);
@@ -400,16 +1026,44 @@ impl Controller {
             
             "help" => {
                 let help = [
+                    "respawn - Get a new ship, after the old one has been destroyed",
                     "list-broadcasts - Lists all received broadcasts",
                     "start-broadcast <text> - Start sending a broadcast",
                     "stop-broadcast - Stop sending the current broadcast",
                     "comm-subscribe/ignore - actively list or ignore new broadcasts",
-                    "nav-data - Print navigation data",
+                    "nav-data - Print navigation data, including the current orbit",
                     "comm-data - Print communication data",
                     "schedule-maneuver <delay (s)> <duration (s)> <degrees> <thrust> - Schedule a maneuver",
+                    "schedule-burn <delay (s)> <duration (s)> <prograde|retrograde|radial-in|radial-out|normal|target|match-velocity> <thrust> [<reference id>] - Schedule a maneuver that follows its orientation",
                     "list-maneuvers - List all scheduled maneuvers",
+                    "update-maneuver <id> <delay (s)> <duration (s)> <degrees> <thrust> - Change a maneuver that hasn't started yet",
                     "cancel-maneuver <id> - Cancel scheduled maneuver",
+                    "plan-rendezvous <target id> <delay (s)> <transfer time (s)> - Plan the maneuvers to meet a target",
+                    "plan-hohmann <body id> <orbit radius> <delay (s)> - Plan a Hohmann transfer to a circular orbit",
+                    "plan-moon-transfer <body id> <moon id> <altitude> <delay (s)> - Plan a Hohmann transfer to a moon",
+                    "execute-plan - Schedule the maneuvers of the last plan",
+                    "closest-approach <id> <time span (s)> - Predict when the ship gets closest to a ship, planet or other object",
                     "ftl-jump <jump time (s)> - Perform a faster-than-light jump",
+                    "list-cargo - List the contents of the cargo hold",
+                    "eject-cargo <index> - Eject cargo into a container",
+                    "list-containers - List nearby cargo containers",
+                    "collect-cargo <id> - Collect a container within range",
+                    "list-wrecks - List nearby wrecks",
+                    "salvage-wreck <id> - Salvage what fits from a wreck within range",
+                    "list-stations - List known stations",
+                    "dock <id> - Dock at a station, after matching its position and velocity",
+                    "undock - Leave the station the ship is docked at",
+                    "refuel - Refill the fuel of the FTL drive while docked",
+                    "repair - Repair the hull while docked",
+                    "dispatch-drones <id> - Send drones to harvest a wreck",
+                    "recall-drones - Recall drones to the ship",
+                    "drone-status - Print the status of the harvesting drones",
+                    "radar <on|off> - Switch the active radar on or off",
+                    "sensor-focus <degrees> <width (degrees)>|off - Focus the sensors",
+                    "sensor-data - Print the status of the sensors",
+                    "launch-missile <target id> - Launch a missile at a target",
+                    "launcher-mode <offensive|defensive> - Set the launcher mode",
+                    "weapons-data - Print the status of the missile launcher",
                     "select-entity <list of ship_id, separate by space>",
                     "clear-selection - Clears currently selected entities",
                 ];
@@ -424,6 +1078,190 @@ impl Controller {
         }
     }
 
+    /// Parses a maneuver orientation. Orientations that are relative to
+    /// another entity require a reference.
+    pub fn parse_orientation(name: &str, reference: Option<EntityId>)
+        -> Option<Orientation>
+    {
+        match (name, reference) {
+            ("prograde"      , _       ) => Some(Orientation::Prograde),
+            ("retrograde"    , _       ) => Some(Orientation::Retrograde),
+            ("radial-in"     , Some(id)) => Some(Orientation::RadialIn(id)),
+            ("radial-out"    , Some(id)) => Some(Orientation::RadialOut(id)),
+            ("normal"        , Some(id)) => Some(Orientation::Normal(id)),
+            ("target"        , Some(id)) => Some(Orientation::Target(id)),
+            ("match-velocity", Some(id)) =>
+                Some(Orientation::MatchVelocity(id)),
+            _                            => None,
+        }
+    }
+
+    fn plan_hohmann(
+        &mut self,
+        frame       : &Frame,
+        body_id     : EntityId,
+        radius      : f64,
+        target_angle: Option<f64>,
+        delay_s     : f64,
+    ) {
+        let (ship, max_thrust, now_s) = match Controller::own_ship(frame) {
+            Some(own_ship) => own_ship,
+            None => {
+                self.console.output.push(format!("No data available."));
+                return;
+            },
+        };
+
+        let center = match frame.planets.get(&body_id) {
+            Some(center) => center,
+            None => {
+                self.console.output.push(format!("Unknown celestial: {}", body_id));
+                return;
+            },
+        };
+
+        let burns = navigation::plan_hohmann(
+            &ship,
+            center,
+            radius,
+            target_angle,
+            now_s,
+            now_s + delay_s,
+        );
+
+        match burns {
+            Some(burns) =>
+                self.show_plan(&burns, ship.mass, max_thrust, now_s),
+            None =>
+                self.console.output.push(format!("No transfer possible. Make sure you're in a roughly circular orbit around the celestial and the target orbit is above its surface.")),
+        }
+    }
+
+    /// Predicting the ship's path takes longer the further it goes, so there
+    /// is a limit. Prints an error and returns `false`, if the time span is
+    /// negative or exceeds that limit.
+    fn check_prediction_span(&mut self, time_s: f64) -> bool {
+        let max_s =
+            navigation::MAX_PREDICTION_STEPS as f64 * PREDICTION_STEP_S;
+
+        if 0.0 <= time_s && time_s <= max_s {
+            return true;
+        }
+
+        self.console.output.push(format!(
+            "Time spans must be between 0 and {:.0} s",
+            max_s,
+        ));
+        false
+    }
+
+    /// Stores the maneuvers that perform the burns of a plan, so they can be
+    /// scheduled later, and prints them.
+    fn show_plan(
+        &mut self,
+        burns     : &[Burn],
+        mass      : f64,
+        max_thrust: f64,
+        now_s     : f64,
+    ) {
+        self.plan = burns
+            .iter()
+            .filter_map(|burn| burn.to_maneuver(mass, max_thrust))
+            .collect();
+
+        self.console.output.push(format!("Planned burns:"));
+        for burn in burns {
+            self.console.output.push(format!(
+                "Time: {:.1} s; Delta-v: {:.2} m/s; Direction: {:.1} degrees",
+                burn.time_s,
+                burn.delta_v.norm(),
+                angle_of(burn.delta_v).to_degrees(),
+            ));
+        }
+
+        // Maneuvers are centered on their burns, so the first one might have
+        // to start before the plan does.
+        if self.plan.first().map_or(false, |first| first.start_s < now_s) {
+            self.console.output.push(format!("Warning: The first burn would have to start in the past. Consider a longer delay."));
+        }
+
+        // Burns take time. If one doesn't end before the next starts, the
+        // plan can't be executed as it is.
+        for pair in self.plan.windows(2) {
+            if pair[0].end_s() > pair[1].start_s {
+                self.console.output.push(format!("Warning: Burns overlap. Consider a longer transfer time."));
+                break;
+            }
+        }
+
+        self.console.output.push(format!("Type \"execute-plan\" to schedule the maneuvers."));
+    }
+
+    /// Returns the body, the maximum thrust and the game time that plans for
+    /// the own ship start from
+    fn own_ship(frame: &Frame) -> Option<(Body, f64, f64)> {
+        match (frame.own_ship(), frame.ship, frame.game_time_s) {
+            (Some(body), Some(ship), Some(now_s)) =>
+                Some((*body, ship.thrust, now_s)),
+            _ =>
+                None,
+        }
+    }
+
+    /// Prints the orbit around the celestial that dominates the ship's
+    /// trajectory. Altitudes are measured from the celestial's surface.
+    fn show_orbit(&mut self, ship: &Body, frame: &Frame) {
+        let dominant =
+            navigation::dominant_planet(ship.position, &frame.planets);
+
+        let (planet_id, planet) = match dominant {
+            Some(dominant) => dominant,
+            None           => return,
+        };
+        let orbit = match Orbit::of(ship, planet) {
+            Some(orbit) => orbit,
+            None        => return,
+        };
+
+        let apoapsis = match orbit.apoapsis {
+            Some(apoapsis) => format!("{:.0}", apoapsis - planet.radius),
+            None           => format!("escape"),
+        };
+        let period = match orbit.period_s {
+            Some(period_s) => format!("{:.0} s", period_s),
+            None           => format!("none"),
+        };
+
+        self.console.output.push(format!(
+            "Orbiting {}: Eccentricity: {:.3}; Periapsis: {:.0}; \
+             Apoapsis: {}; Period: {}",
+            planet_id,
+            orbit.eccentricity,
+            orbit.periapsis - planet.radius,
+            apoapsis,
+            period,
+        ));
+
+        if let Some((apsis, time_s)) = orbit.next_apsis {
+            let apsis = match apsis {
+                Apsis::Periapsis => "Periapsis",
+                Apsis::Apoapsis  => "Apoapsis",
+            };
+
+            self.console.output.push(format!(
+                "Next apsis: {} in {:.0} s",
+                apsis, time_s,
+            ));
+        }
+
+        if orbit.intersects_surface {
+            self.console.output.push(format!(
+                "Warning: Orbit intersects the surface of {}!",
+                planet_id,
+            ));
+        }
+    }
+
     /// parses entity ids from args
     /// does not check if entityid currently exists
     pub fn parse_entity_ids(args: &str) -> Vec<EntityId> {
@@ -0,0 +1,78 @@
+use nalgebra::{
+    cast,
+    Vec2,
+};
+
+use client::graphics::base::Graphics;
+use client::graphics::draw::{
+    GlyphDrawer,
+    ShapeDrawer,
+};
+use client::graphics::transforms::Transforms;
+use client::interface::Frame;
+use shared::color;
+
+
+/// Draws objects that have been detected, but not identified.
+pub struct ContactDrawer {
+    size: f32,
+
+    symbol_drawer: ShapeDrawer,
+    glyph_drawer : GlyphDrawer,
+}
+
+impl ContactDrawer {
+    pub fn new(
+        graphics : &mut Graphics,
+        size     : f32,
+        font_size: f32,
+    ) -> ContactDrawer {
+        ContactDrawer {
+            size: size,
+
+            symbol_drawer: ShapeDrawer::contact(graphics),
+            glyph_drawer : GlyphDrawer::new(graphics, font_size as u32),
+        }
+    }
+
+    pub fn draw(
+        &mut self,
+        frame     : &Frame,
+        transforms: &Transforms,
+        graphics  : &mut Graphics,
+    ) {
+        for (id, contact) in &frame.contacts {
+            let transform =
+                transforms.symbol_to_screen(cast(contact.position));
+
+            if frame.select_ids.contains(id) {
+                self.symbol_drawer.draw(
+                    self.size * 1.5,
+                    color::Colors::white(),
+                    transform,
+                    graphics,
+                );
+            }
+
+            self.symbol_drawer.draw(
+                self.size,
+                color::Colors::yellow(),
+                transform,
+                graphics,
+            );
+
+            self.glyph_drawer.draw(
+                &format!("{}: {} (+/- {:.0})",
+                    id,
+                    contact.classification,
+                    contact.uncertainty,
+                ),
+                Vec2::new(0.0, self.size),
+                color::Colors::white(),
+                true,
+                transform,
+                graphics,
+            );
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use nalgebra::{
+    cast,
+    Vec2,
+};
+
+use client::graphics::base::Graphics;
+use client::graphics::draw::{
+    GlyphDrawer,
+    ShapeDrawer,
+};
+use client::graphics::transforms::Transforms;
+use client::interface::Frame;
+use shared::color;
+
+
+pub struct ContainerDrawer {
+    size: f32,
+
+    symbol_drawer: ShapeDrawer,
+    glyph_drawer : GlyphDrawer,
+}
+
+impl ContainerDrawer {
+    pub fn new(
+        graphics : &mut Graphics,
+        size     : f32,
+        font_size: f32,
+    ) -> ContainerDrawer {
+        ContainerDrawer {
+            size: size,
+
+            symbol_drawer: ShapeDrawer::container(graphics),
+            glyph_drawer : GlyphDrawer::new(graphics, font_size as u32),
+        }
+    }
+
+    pub fn draw(
+        &mut self,
+        frame     : &Frame,
+        transforms: &Transforms,
+        graphics  : &mut Graphics,
+    ) {
+        for (id, body) in &frame.containers {
+            let transform = transforms.symbol_to_screen(cast(body.position));
+
+            if frame.select_ids.contains(id) {
+                self.symbol_drawer.draw(
+                    self.size * 1.5,
+                    color::Colors::white(),
+                    transform,
+                    graphics,
+                );
+            }
+
+            self.symbol_drawer.draw(
+                self.size,
+                color::Colors::orange(),
+                transform,
+                graphics,
+            );
+
+            self.glyph_drawer.draw(
+                &id.to_string(),
+                Vec2::new(0.0, self.size),
+                color::Colors::white(),
+                true,
+                transform,
+                graphics,
+            );
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use nalgebra::cast;
+
+use client::graphics::base::Graphics;
+use client::graphics::draw::ShapeDrawer;
+use client::graphics::transforms::Transforms;
+use client::interface::Frame;
+use shared::color;
+
+
+pub struct MissileDrawer {
+    size: f32,
+
+    symbol_drawer: ShapeDrawer,
+}
+
+impl MissileDrawer {
+    pub fn new(graphics: &mut Graphics, size: f32) -> MissileDrawer {
+        MissileDrawer {
+            size: size,
+
+            symbol_drawer: ShapeDrawer::ship(graphics),
+        }
+    }
+
+    pub fn draw(
+        &mut self,
+        frame     : &Frame,
+        transforms: &Transforms,
+        graphics  : &mut Graphics,
+    ) {
+        for (_, body) in &frame.missiles {
+            let transform = transforms.symbol_to_screen(cast(body.position));
+
+            self.symbol_drawer.draw(
+                self.size,
+                color::Colors::red(),
+                transform,
+                graphics,
+            );
+        }
+    }
+}
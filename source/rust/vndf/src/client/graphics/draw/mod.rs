@@ -1,15 +1,27 @@
 mod console;
+mod contact;
+mod container;
 mod glyphs;
+mod missile;
 mod path;
+mod sensors;
 mod shapes;
+mod station;
 mod ship;
 mod planet;
 mod grid;
+mod wreck;
 
 pub use self::console::ConsoleDrawer;
+pub use self::contact::ContactDrawer;
+pub use self::container::ContainerDrawer;
 pub use self::glyphs::GlyphDrawer;
+pub use self::missile::MissileDrawer;
 pub use self::path::PathDrawer;
+pub use self::sensors::SensorDrawer;
 pub use self::shapes::ShapeDrawer;
+pub use self::station::StationDrawer;
 pub use self::ship::ShipDrawer;
 pub use self::planet::PlanetDrawer;
 pub use self::grid::GridDrawer;
+pub use self::wreck::WreckDrawer;
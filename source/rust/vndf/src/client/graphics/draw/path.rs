@@ -3,11 +3,15 @@ use nalgebra::{
     Iso3,
     Norm,
     ToHomogeneous,
+    Vec2,
     Vec3,
 };
 
 use client::graphics::base::Graphics;
-use client::graphics::draw::ShapeDrawer;
+use client::graphics::draw::{
+    GlyphDrawer,
+    ShapeDrawer,
+};
 use client::graphics::transforms::Transforms;
 use client::interface::Frame;
 use shared::color::Colors;
@@ -15,17 +19,42 @@ use shared::game::logic::{
     apply_gravity,
     integrate,
 };
+use shared::game::navigation::{
+    closest_approach,
+    Target,
+};
 use shared::util::angle_of;
 
 
+/// Number of steps of the predicted path
+const PATH_STEPS: usize = 1000;
+
+/// Duration of each step of the predicted path
+const PATH_STEP_S: f64 = 10.0;
+
+
+/// Draws the predicted path of the own ship, with markers where it gets
+/// closest to the selected entities.
 pub struct PathDrawer {
-    line_drawer: ShapeDrawer,
+    marker_size: f32,
+
+    line_drawer  : ShapeDrawer,
+    marker_drawer: ShapeDrawer,
+    glyph_drawer : GlyphDrawer,
 }
 
 impl PathDrawer {
-    pub fn new(graphics: &mut Graphics) -> Self {
+    pub fn new(
+        graphics   : &mut Graphics,
+        marker_size: f32,
+        font_size  : f32,
+    ) -> Self {
         PathDrawer {
-            line_drawer: ShapeDrawer::line(graphics),
+            marker_size: marker_size,
+
+            line_drawer  : ShapeDrawer::line(graphics),
+            marker_drawer: ShapeDrawer::contact(graphics),
+            glyph_drawer : GlyphDrawer::new(graphics, font_size as u32),
         }
     }
 
@@ -43,12 +72,14 @@ impl PathDrawer {
             None     => return,
         };
 
+        self.draw_approaches(frame, transforms, graphics);
+
         let mut previous_position = ship.position;
-        for _ in 0 .. 1000 {
+        for _ in 0 .. PATH_STEPS {
             for (_, planet) in &frame.planets {
                 apply_gravity(planet, &mut ship);
             }
-            integrate(&mut ship, 10.0);
+            integrate(&mut ship, PATH_STEP_S);
 
             let movement = previous_position - ship.position;
             previous_position = ship.position;
@@ -76,4 +107,77 @@ impl PathDrawer {
             );
         }
     }
+
+    fn draw_approaches(
+        &mut self,
+        frame     : &Frame,
+        transforms: &Transforms,
+        graphics  : &mut Graphics,
+    ) {
+        let (ship, now_s) = match (frame.own_ship(), frame.game_time_s) {
+            (Some(ship), Some(now_s)) => (*ship, now_s),
+            _                         => return,
+        };
+
+        let planets: Vec<_> = frame.planets.values().cloned().collect();
+
+        for id in &frame.select_ids {
+            if Some(*id) == frame.ship_id {
+                continue;
+            }
+
+            // Distances to planets are measured from their surface.
+            let (target, radius) = match frame.planets.get(id) {
+                Some(planet) =>
+                    (Target::Fixed(planet.position), planet.radius),
+                None => match frame.find_body(*id) {
+                    Some(body) => (Target::Body(*body), 0.0),
+                    None       => continue,
+                },
+            };
+
+            let approach = closest_approach(
+                &ship,
+                target,
+                &planets,
+                now_s,
+                PATH_STEPS as f64 * PATH_STEP_S,
+                PATH_STEP_S,
+            );
+            let approach = match approach {
+                Some(approach) => approach,
+                None           => continue,
+            };
+
+            let transform =
+                transforms.symbol_to_screen(cast(approach.position));
+
+            self.marker_drawer.draw(
+                self.marker_size,
+                Colors::yellow(),
+                transform,
+                graphics,
+            );
+            let label = if approach.distance <= radius {
+                format!("{}: impact in {:.0} s", id, approach.time_s - now_s)
+            }
+            else {
+                format!(
+                    "{}: {:.0} m in {:.0} s",
+                    id,
+                    approach.distance - radius,
+                    approach.time_s - now_s,
+                )
+            };
+
+            self.glyph_drawer.draw(
+                &label,
+                Vec2::new(0.0, self.marker_size),
+                Colors::yellow(),
+                true,
+                transform,
+                graphics,
+            );
+        }
+    }
 }
@@ -0,0 +1,75 @@
+use nalgebra::{
+    Iso3,
+    ToHomogeneous,
+    Vec3,
+};
+
+use client::graphics::base::Graphics;
+use client::graphics::draw::ShapeDrawer;
+use client::graphics::transforms::Transforms;
+use client::interface::Frame;
+use shared::color::Colors;
+
+
+/// Draws the edges of the cone that the player's sensors are focused on.
+pub struct SensorDrawer {
+    line_drawer: ShapeDrawer,
+}
+
+impl SensorDrawer {
+    pub fn new(graphics: &mut Graphics) -> Self {
+        SensorDrawer {
+            line_drawer: ShapeDrawer::line(graphics),
+        }
+    }
+
+    pub fn draw(
+        &mut self,
+        frame     : &Frame,
+        transforms: &Transforms,
+        graphics  : &mut Graphics,
+    ) {
+        let ship = match frame.ship_id {
+            Some(id) => match frame.ships.get(&id) {
+                Some(&ship) => ship,
+                None        => return,
+            },
+            None     => return,
+        };
+        let (sensors, focus) = match frame.sensors {
+            Some(sensors) => match sensors.focus {
+                Some(focus) => (sensors, focus),
+                None        => return,
+            },
+            None => return,
+        };
+
+        let length = sensors.range * focus.boost();
+
+        for &edge in &[-0.5, 0.5] {
+            let angle = focus.angle + focus.width * edge;
+
+            let transform: Iso3<f32> = Iso3::new(
+                Vec3::new(
+                    ship.position.x as f32,
+                    ship.position.y as f32,
+                    0.0
+                ),
+                Vec3::new(
+                    0.0,
+                    0.0,
+                    angle as f32,
+                ),
+            );
+
+            self.line_drawer.draw(
+                length as f32,
+                Colors::green_spring(),
+                transforms.camera_to_screen
+                    * transforms.world_to_camera
+                    * transform.to_homogeneous(),
+                graphics,
+            );
+        }
+    }
+}
@@ -107,6 +107,34 @@ impl ShapeDrawer {
             )
     }
 
+    pub fn container(graphics: &mut Graphics) -> ShapeDrawer {
+        ShapeDrawer::new(
+            graphics,
+            gfx::PrimitiveType::TriangleStrip,
+            &[
+                Vertex { pos: [ -0.5, -0.5 ] },
+                Vertex { pos: [  0.5, -0.5 ] },
+                Vertex { pos: [ -0.5,  0.5 ] },
+                Vertex { pos: [  0.5,  0.5 ] },
+                ],
+            )
+    }
+
+    /// An outlined diamond, which sets contacts apart from identified objects
+    pub fn contact(graphics: &mut Graphics) -> ShapeDrawer {
+        ShapeDrawer::new(
+            graphics,
+            gfx::PrimitiveType::LineStrip,
+            &[
+                Vertex { pos: [  0.0, -0.5 ] },
+                Vertex { pos: [  0.5,  0.0 ] },
+                Vertex { pos: [  0.0,  0.5 ] },
+                Vertex { pos: [ -0.5,  0.0 ] },
+                Vertex { pos: [  0.0, -0.5 ] },
+                ],
+            )
+    }
+
     pub fn line(graphics: &mut Graphics) -> ShapeDrawer {
         ShapeDrawer::new(
             graphics,
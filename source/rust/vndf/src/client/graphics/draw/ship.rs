@@ -16,7 +16,15 @@ use shared::color;
 use shared::game::data::{
     Body,
     EntityId,
+    Hull,
+    Planet,
 };
+use shared::game::navigation::{
+    self,
+    Apsis,
+    Orbit,
+};
+use shared::physics::SpatialIndex;
 use shared::physics::collision::{SphereCollider};
 
 
@@ -98,6 +106,8 @@ impl ShipDrawer {
 
             self.draw_info(
                 ship,
+                frame.hulls.get(ship_id),
+                orbit_of(ship, frame),
                 transform,
                 graphics,
             );
@@ -126,6 +136,8 @@ impl ShipDrawer {
 
             self.draw_info(
                 &avg_ship,
+                None,
+                None,
                 transform,
                 graphics,
             );
@@ -223,6 +235,8 @@ impl ShipDrawer {
     fn draw_info(
         &mut self,
         ship     : &Body,
+        hull     : Option<&Hull>,
+        orbit    : Option<(Orbit, Planet)>,
         transform: Mat4<f32>,
         graphics : &mut Graphics,
     ) {
@@ -255,12 +269,67 @@ impl ShipDrawer {
 
         draw(&position);
         draw(&velocity);
+
+        if let Some(hull) = hull {
+            let hull = format!("hull: {:.0}%",
+                hull.integrity / hull.max_integrity * 100.0,
+            );
+
+            draw(&hull);
+        }
+
+        // Altitudes are measured from the surface of the orbited celestial
+        if let Some((orbit, planet)) = orbit {
+            let apsides = match orbit.apoapsis {
+                Some(apoapsis) =>
+                    format!("orbit: {:.0} x {:.0}",
+                        orbit.periapsis - planet.radius,
+                        apoapsis - planet.radius,
+                    ),
+                None =>
+                    format!("orbit: {:.0} x escape",
+                        orbit.periapsis - planet.radius,
+                    ),
+            };
+            let eccentricity = format!("ecc: {:.3}", orbit.eccentricity);
+
+            draw(&apsides);
+            draw(&eccentricity);
+
+            if let Some(period_s) = orbit.period_s {
+                draw(&format!("period: {:.0} s", period_s));
+            }
+            if let Some((apsis, time_s)) = orbit.next_apsis {
+                let apsis = match apsis {
+                    Apsis::Periapsis => "peri",
+                    Apsis::Apoapsis  => "apo",
+                };
+
+                draw(&format!("{} in: {:.0} s", apsis, time_s));
+            }
+            if orbit.intersects_surface {
+                draw("IMPACT");
+            }
+        }
     }
 }
 
 fn check_visual_collision(frame: &Frame,
                           set: &mut Vec<Vec<EntityId>>,
                           zoom: f32) {
+    // the biggest ship collider determines how far apart ships can be and
+    // still overlap. planets have colliders too, but they're way bigger and
+    // would make the grid useless.
+    let max_radius = frame.ships.keys()
+        .filter_map(|ship_id| frame.colliders.get(ship_id))
+        .fold(0.0, |max: f32, coll| max.max(coll.radius()));
+    if max_radius <= 0.0 { return } // no colliders yet
+
+    let mut index = SpatialIndex::new((max_radius * zoom * 2.0) as f64);
+    for (ship_id,ship_body) in frame.ships.iter() {
+        index.insert(*ship_id, ship_body.position);
+    }
+
     'ships: for (ship_id,ship_body) in frame.ships.iter() {
         let ship_coll = {
             if let Some (coll) = frame.colliders.get(&ship_id) { coll }
@@ -268,9 +337,13 @@ fn check_visual_collision(frame: &Frame,
                    continue 'ships }
         };
 
-        // check ships colliding into eachother
-        'other_ships: for (ship_id2,ship_body2) in frame.ships.iter() {
-            if ship_id == ship_id2 { continue 'other_ships }
+        let reach = ((ship_coll.radius() + max_radius) * zoom) as f64;
+
+        // check nearby ships colliding into eachother
+        'other_ships: for ship_id2 in index.neighbours(ship_body.position,
+                                                       reach) {
+            if *ship_id == ship_id2 { continue 'other_ships }
+            let ship_body2 = &frame.ships[&ship_id2];
             
             let ship_coll2 = {
                 if let Some (coll) = frame.colliders.get(&ship_id2) { coll }
@@ -290,6 +363,15 @@ fn check_visual_collision(frame: &Frame,
 }
 
 
+/// Returns the ship's orbit around the celestial that dominates its trajectory
+fn orbit_of(ship: &Body, frame: &Frame) -> Option<(Orbit, Planet)> {
+    navigation::dominant_planet(ship.position, &frame.planets)
+        .and_then(|(_, planet)|
+            Orbit::of(ship, planet).map(|orbit| (orbit, *planet))
+        )
+}
+
+
 /// insert two entities that relate into same group in set
 fn set_insert_pair (set: &mut Vec<Vec<EntityId>>,
                     ents: (&EntityId,&EntityId)) {
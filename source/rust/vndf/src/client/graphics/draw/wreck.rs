@@ -0,0 +1,73 @@
+use nalgebra::{
+    cast,
+    Vec2,
+};
+
+use client::graphics::base::Graphics;
+use client::graphics::draw::{
+    GlyphDrawer,
+    ShapeDrawer,
+};
+use client::graphics::transforms::Transforms;
+use client::interface::Frame;
+use shared::color;
+
+
+/// Draws the wrecks of destroyed ships.
+pub struct WreckDrawer {
+    size: f32,
+
+    symbol_drawer: ShapeDrawer,
+    glyph_drawer : GlyphDrawer,
+}
+
+impl WreckDrawer {
+    pub fn new(
+        graphics : &mut Graphics,
+        size     : f32,
+        font_size: f32,
+    ) -> WreckDrawer {
+        WreckDrawer {
+            size: size,
+
+            symbol_drawer: ShapeDrawer::ship_group(graphics),
+            glyph_drawer : GlyphDrawer::new(graphics, font_size as u32),
+        }
+    }
+
+    pub fn draw(
+        &mut self,
+        frame     : &Frame,
+        transforms: &Transforms,
+        graphics  : &mut Graphics,
+    ) {
+        for (id, body) in &frame.wrecks {
+            let transform = transforms.symbol_to_screen(cast(body.position));
+
+            if frame.select_ids.contains(id) {
+                self.symbol_drawer.draw(
+                    self.size * 1.5,
+                    color::Colors::white(),
+                    transform,
+                    graphics,
+                );
+            }
+
+            self.symbol_drawer.draw(
+                self.size,
+                color::Colors::grey_light(),
+                transform,
+                graphics,
+            );
+
+            self.glyph_drawer.draw(
+                &id.to_string(),
+                Vec2::new(0.0, self.size),
+                color::Colors::white(),
+                true,
+                transform,
+                graphics,
+            );
+        }
+    }
+}
@@ -3,10 +3,16 @@ use client::graphics::SHIP_SIZE;
 use client::graphics::frame_state::FrameState;
 use client::graphics::draw::{
     ConsoleDrawer,
+    ContactDrawer,
+    ContainerDrawer,
+    MissileDrawer,
+    SensorDrawer,
     ShipDrawer,
+    StationDrawer,
     PlanetDrawer,
     GridDrawer,
     PathDrawer,
+    WreckDrawer,
 };
 use client::graphics::camera::{Camera};
 use client::interface::Frame;
@@ -14,11 +20,17 @@ use client::window::Window;
 
 
 pub struct Renderer {
-    console_drawer: ConsoleDrawer,
-    ship_drawer   : ShipDrawer,
-    planet_drawer : PlanetDrawer,
-    grid_drawer   : GridDrawer,
-    path_drawer   : PathDrawer,
+    console_drawer  : ConsoleDrawer,
+    contact_drawer  : ContactDrawer,
+    container_drawer: ContainerDrawer,
+    missile_drawer  : MissileDrawer,
+    sensor_drawer   : SensorDrawer,
+    ship_drawer     : ShipDrawer,
+    station_drawer  : StationDrawer,
+    planet_drawer   : PlanetDrawer,
+    grid_drawer     : GridDrawer,
+    path_drawer     : PathDrawer,
+    wreck_drawer    : WreckDrawer,
     
     pub camera: Camera,
 }
@@ -34,11 +46,33 @@ impl Renderer {
             &mut graphics,
             font_height,
         );
+        let contact_drawer = ContactDrawer::new(
+            &mut graphics,
+            ship_size * 0.6,
+            font_height,
+        );
+        let container_drawer = ContainerDrawer::new(
+            &mut graphics,
+            ship_size * 0.3,
+            font_height,
+        );
+        let missile_drawer = MissileDrawer::new(
+            &mut graphics,
+            ship_size * 0.3,
+        );
+        let sensor_drawer = SensorDrawer::new(
+            &mut graphics,
+        );
         let ship_drawer = ShipDrawer::new(
             &mut graphics,
             ship_size,
             font_height,
         );
+        let station_drawer = StationDrawer::new(
+            &mut graphics,
+            ship_size * 1.5,
+            font_height,
+        );
         let planet_drawer = PlanetDrawer::new(
             &mut graphics,
             font_height,
@@ -49,14 +83,27 @@ impl Renderer {
         );
         let path_drawer = PathDrawer::new(
             &mut graphics,
+            ship_size * 0.5,
+            font_height,
+        );
+        let wreck_drawer = WreckDrawer::new(
+            &mut graphics,
+            ship_size * 0.5,
+            font_height,
         );
 
         Renderer {
-            console_drawer: console_drawer,
-            ship_drawer   : ship_drawer,
-            planet_drawer : planet_drawer,
-            grid_drawer   : grid_drawer,
-            path_drawer   : path_drawer,
+            console_drawer  : console_drawer,
+            contact_drawer  : contact_drawer,
+            container_drawer: container_drawer,
+            missile_drawer  : missile_drawer,
+            sensor_drawer   : sensor_drawer,
+            ship_drawer     : ship_drawer,
+            station_drawer  : station_drawer,
+            planet_drawer   : planet_drawer,
+            grid_drawer     : grid_drawer,
+            path_drawer     : path_drawer,
+            wreck_drawer    : wreck_drawer,
 
             camera: Camera::new(),
         }
@@ -89,12 +136,48 @@ impl Renderer {
             &mut frame_state.graphics,
         );
 
+        self.sensor_drawer.draw(
+            frame,
+            &frame_state.transforms,
+            &mut frame_state.graphics,
+        );
+
         self.planet_drawer.draw(frame,
             self.camera.zoom,
             &frame_state.transforms,
             &mut frame_state.graphics,
         );
 
+        self.contact_drawer.draw(
+            frame,
+            &frame_state.transforms,
+            &mut frame_state.graphics,
+        );
+
+        self.container_drawer.draw(
+            frame,
+            &frame_state.transforms,
+            &mut frame_state.graphics,
+        );
+
+        self.station_drawer.draw(
+            frame,
+            &frame_state.transforms,
+            &mut frame_state.graphics,
+        );
+
+        self.wreck_drawer.draw(
+            frame,
+            &frame_state.transforms,
+            &mut frame_state.graphics,
+        );
+
+        self.missile_drawer.draw(
+            frame,
+            &frame_state.transforms,
+            &mut frame_state.graphics,
+        );
+
         self.ship_drawer.draw(
             frame,
             self.camera.zoom,
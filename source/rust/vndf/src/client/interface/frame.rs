@@ -10,22 +10,52 @@ use ncollide::shape::Ball;
 use client::graphics::camera::CameraTrack;
 use shared::game::data::{
     Body,
+    Cargo,
+    Contact,
+    Container,
+    Docking,
+    DroneBay,
     EntityId,
+    Hull,
+    Launcher,
     ManeuverData,
     Planet,
+    Sensors,
+    Ship,
+    Wreck,
 };
 
 
 #[derive(Clone, Debug, RustcDecodable, RustcEncodable)]
 pub struct Frame {
     pub ship_id     : Option<EntityId>,
+    pub ship        : Option<Ship>, // propulsion and size of the own ship
     pub game_time_s : Option<f64>,
     pub message     : Message,
 
     pub ships    : BTreeMap<EntityId, Body>,
+    pub hulls    : BTreeMap<EntityId, Hull>,
     pub planets  : BTreeMap<EntityId, Planet>,
     pub maneuvers: BTreeMap<EntityId, ManeuverData>,
 
+    pub cargo     : Option<Cargo>,
+    pub containers: BTreeMap<EntityId, Body>,
+    pub contents  : BTreeMap<EntityId, Container>,
+
+    pub wrecks        : BTreeMap<EntityId, Body>,
+    pub wreck_contents: BTreeMap<EntityId, Wreck>,
+    pub drone_bay     : Option<DroneBay>,
+
+    pub stations     : BTreeMap<EntityId, Body>,
+    pub station_names: BTreeMap<EntityId, String>,
+    pub docking      : Option<Docking>,
+
+    pub launcher: Option<Launcher>,
+    pub missiles: BTreeMap<EntityId, Body>,
+
+    pub sensors : Option<Sensors>,
+    pub contacts: BTreeMap<EntityId, Contact>,
+
     pub broadcasts: HashMap<EntityId, String>,
 
     pub colliders   : HashMap<EntityId, Ball<f32>>,
@@ -39,13 +69,33 @@ impl Frame {
     pub fn new() -> Frame {
         Frame {
             ship_id     : None,
+            ship        : None,
             game_time_s : None,
             message     : Message::None,
             
             ships    : BTreeMap::new(),
+            hulls    : BTreeMap::new(),
             planets  : BTreeMap::new(),
             maneuvers: BTreeMap::new(),
 
+            cargo     : None,
+            containers: BTreeMap::new(),
+            contents  : BTreeMap::new(),
+
+            wrecks        : BTreeMap::new(),
+            wreck_contents: BTreeMap::new(),
+            drone_bay     : None,
+
+            stations     : BTreeMap::new(),
+            station_names: BTreeMap::new(),
+            docking      : None,
+
+            launcher: None,
+            missiles: BTreeMap::new(),
+
+            sensors : None,
+            contacts: BTreeMap::new(),
+
             broadcasts: HashMap::new(),
 
             colliders   : HashMap::new(),
@@ -73,6 +123,16 @@ impl Frame {
             None     => None,
         }
     }
+
+    /// Returns the body of any entity that the own ship could navigate to.
+    /// Planets are not included, as they don't have a body.
+    pub fn find_body(&self, id: EntityId) -> Option<&Body> {
+        self.ships.get(&id)
+            .or_else(|| self.stations.get(&id))
+            .or_else(|| self.wrecks.get(&id))
+            .or_else(|| self.containers.get(&id))
+            .or_else(|| self.missiles.get(&id))
+    }
 }
 
 
@@ -4,18 +4,44 @@ use rustc_serialize::json::{
 };
 
 use client::graphics::camera::CameraTrack;
-use shared::game::data::{ManeuverData,EntityId};
+use shared::game::data::{
+    EntityId,
+    LauncherMode,
+    ManeuverData,
+    SensorFocus,
+};
 
 #[derive(Clone, Debug, RustcDecodable, RustcEncodable, PartialEq)]
 pub enum InputEvent {
+    Respawn,
+
     StartBroadcast(String),
     StopBroadcast,
 
     ScheduleManeuver(ManeuverData),
+    UpdateManeuver(EntityId, ManeuverData),
     CancelManeuver(EntityId),
 
     FtlJump(f64),
 
+    EjectCargo(usize),
+    CollectCargo(EntityId),
+    SalvageWreck(EntityId),
+
+    DispatchDrones(EntityId),
+    RecallDrones,
+
+    Dock(EntityId),
+    Undock,
+    Refuel,
+    Repair,
+
+    LaunchMissile(EntityId),
+    SetLauncherMode(LauncherMode),
+
+    SetRadar(bool),
+    SetSensorFocus(Option<SensorFocus>),
+
     Track(CameraTrack), // sets camera tracking
     
     Select(Vec<EntityId>),
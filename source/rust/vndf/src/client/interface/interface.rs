@@ -19,7 +19,7 @@ use glutin::Event::{Closed};
 
 use nalgebra::cast;
 
-use shared::physics::{SphereCollider};
+use shared::physics::{SpatialIndex,SphereCollider};
 
 use client::config::Config;
 use client::console;
@@ -124,7 +124,14 @@ impl Interface for Player {
 
 fn check_collisions(frame: &mut Frame,
                     zoom: f32) {
-    // TODO: needs some notion of space-partitioning for efficiency
+    // ships can only touch, if they're closer than two radii
+    let reach = (SHIP_SIZE * zoom * 2.0) as f64;
+
+    let mut index = SpatialIndex::new(reach);
+    for (ship_id,ship_body) in frame.ships.iter() {
+        index.insert(*ship_id, ship_body.position);
+    }
+
     'ships: for (ship_id,ship_body) in frame.ships.iter() {
         if let Some (_) = frame.colliders.get(&ship_id) {
             // collision
@@ -134,9 +141,11 @@ fn check_collisions(frame: &mut Frame,
             continue 'ships
         }
 
-        // check ships colliding into eachother
-        'other_ships: for (ship_id2,ship_body2) in frame.ships.iter() {
-            if ship_id == ship_id2 { continue 'other_ships }
+        // check nearby ships colliding into eachother
+        'other_ships: for ship_id2 in index.neighbours(ship_body.position,
+                                                       reach) {
+            if *ship_id == ship_id2 { continue 'other_ships }
+            let ship_body2 = &frame.ships[&ship_id2];
             
             if let Some (_) = frame.colliders.get(&ship_id2) {
                 // collision
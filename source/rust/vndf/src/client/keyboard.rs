@@ -9,7 +9,10 @@ use client::interface::{
     Frame,
     InputEvent,
 };
-use shared::game::data::{ManeuverData};
+use shared::game::data::{
+    ManeuverData,
+    Orientation,
+};
 
 /// Keyboard Input Controller
 pub struct Keyboard {
@@ -89,10 +92,11 @@ impl Keyboard {
                         }
                         else {return} };
                     let data = ManeuverData {
-                        start_s   : game_time_s + 0.0,
-                        duration_s: random::<u8>() as f64,
-                        angle     : direction_rad,
-                        thrust    : 1.0,
+                        start_s    : game_time_s + 0.0,
+                        duration_s : random::<u8>() as f64,
+                        angle      : direction_rad,
+                        thrust     : 1.0,
+                        orientation: Orientation::Fixed,
                     };
 
                     events.push(InputEvent::ScheduleManeuver(data));
@@ -22,6 +22,16 @@ impl Clients {
 		self.clients.get_mut(address)
 	}
 
+	pub fn address_of(&self, ship_id: EntityId) -> Option<SocketAddr> {
+		for (&address, client) in &self.clients {
+			if client.ship_id == ship_id {
+				return Some(address);
+			}
+		}
+
+		None
+	}
+
 	pub fn remove_inactive<F>(
 		&mut self,
 		    now_s    : f64,
@@ -53,5 +63,7 @@ impl Clients {
 #[derive(Debug)]
 pub struct Client {
 	pub ship_id      : EntityId,
+	pub design       : String, // design of the ship, used when respawning
+	pub spawner      : Option<String>, // spawner that was picked on login
 	pub last_active_s: f64,
 }
@@ -1,17 +1,75 @@
+use std::f64::consts::PI;
+
 use nalgebra::Vec2;
+use rand::thread_rng;
+use rand::distributions::{
+	Range,
+	Sample,
+};
+
+use shared::game::data::EntityId;
+
 
+/// Name of the spawner that is used if a player doesn't pick one
+pub const DEFAULT_SPAWNER: &'static str = "default";
 
-#[derive(Clone, Copy, Debug, RustcDecodable, RustcEncodable)]
+
+/// A place where ships enter the game
+#[derive(Clone, Debug, RustcDecodable, RustcEncodable)]
 pub struct Spawner {
+	pub name    : String,
 	pub position: Vec2<f64>,
 	pub velocity: Vec2<f64>,
+	pub jitter  : f64, // max distance of new ships from the position
 }
 
 impl Spawner {
 	pub fn new() -> Self {
 		Spawner {
+			name    : DEFAULT_SPAWNER.to_string(),
 			position: Vec2::new(0.0, 0.0),
 			velocity: Vec2::new(1.0, 0.0),
+			jitter  : 0.0,
+		}
+	}
+
+	/// Returns a position for a new ship. Ships are spread out randomly
+	/// within the jitter radius, so they don't overlap when they enter at the
+	/// same time.
+	pub fn spawn_position(&self) -> Vec2<f64> {
+		if self.jitter <= 0.0 {
+			return self.position;
+		}
+
+		let mut rng = thread_rng();
+
+		let angle    = Range::new(0.0, 2.0 * PI).sample(&mut rng);
+		let distance = Range::new(0.0, self.jitter).sample(&mut rng);
+
+		self.position + Vec2::new(angle.cos(), angle.sin()) * distance
+	}
+}
+
+
+/// An AI-controlled von Neumann probe. Probes patrol a list of waypoints and
+/// pursue the ships they detect.
+#[derive(Clone, Debug, RustcDecodable, RustcEncodable)]
+pub struct Probe {
+	pub patrol         : Vec<Vec2<f64>>,
+	pub waypoint       : usize, // index of the waypoint the probe heads for
+	pub pursuit_range  : f64,
+	pub target         : Option<EntityId>,
+	pub next_decision_s: f64,
+}
+
+impl Probe {
+	pub fn new(patrol: Vec<Vec2<f64>>, pursuit_range: f64) -> Probe {
+		Probe {
+			patrol         : patrol,
+			waypoint       : 0,
+			pursuit_range  : pursuit_range,
+			target         : None,
+			next_decision_s: 0.0,
 		}
 	}
 }
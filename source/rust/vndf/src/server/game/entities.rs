@@ -1,10 +1,23 @@
+use server::game::data::Probe;
 use shared::game::data::{
     Body,
     Broadcast,
+    Cargo,
+    Container,
+    Docking,
+    DroneBay,
     EntityId,
+    FtlDrive,
+    Hull,
+    Launcher,
     Maneuver,
+    Missile,
     Planet,
+    Sensors,
     Ship,
+    Signature,
+    Station,
+    Wreck,
 };
 
 
@@ -1,5 +1,11 @@
-use nalgebra::Vec2;
+use std::f64::consts::PI;
 
+use nalgebra::{
+    Norm,
+    Vec2,
+};
+
+use server::game::data::Probe;
 use server::game::state::{
     GameEvent,
     GameState,
@@ -8,36 +14,169 @@ use server::game::systems;
 use shared::game::data::{
     Body,
     Broadcast,
+    Cargo,
+    CargoItem,
+    CargoKind,
+    Container,
+    DetectionQuality,
+    Docking,
+    DroneBay,
     EntityId,
+    FtlDrive,
+    Hull,
+    Launcher,
+    LauncherMode,
     Maneuver,
     ManeuverData,
-    Ship,
+    Missile,
+    SensorFocus,
+    Signature,
+};
+use shared::game::design::{
+    DEFAULT_DESIGN,
+    ModuleKind,
+    ShipDesign,
 };
 use shared::game::logic::{
     apply_gravity,
     integrate,
+    sweep_circle,
 };
+use shared::protocol::server;
+
+
+/// Maximum distance between a ship and a container it collects
+pub const COLLECT_RANGE: f64 = 100.0;
+
+/// Maximum distance between a ship and the station it docks at
+pub const DOCKING_RANGE: f64 = 50.0;
+
+/// Maximum speed of a ship relative to the station it docks at
+pub const DOCKING_SPEED: f64 = 1.0;
 
+/// Distance to a planet's surface at which an FTL jump is aborted
+pub const FTL_ABORT_DISTANCE: f64 = 10.0;
 
-pub struct Enter;
+/// Maximum number of maneuvers a ship can have scheduled at the same time
+pub const MAX_QUEUED_MANEUVERS: usize = 10;
+
+/// How far in the past a maneuver may start. Maneuvers that are meant to start
+/// immediately are a bit late by the time they arrive at the server.
+pub const MANEUVER_START_TOLERANCE_S: f64 = 1.0;
+
+
+pub struct Enter {
+    pub design : String,
+    pub spawner: Option<String>, // the first spawner is used, if `None`
+}
+
+impl Enter {
+    /// Creates an `Enter` event for a ship of the default design.
+    pub fn new() -> Enter {
+        Enter {
+            design : DEFAULT_DESIGN.to_string(),
+            spawner: None,
+        }
+    }
+}
 
 impl GameEvent for Enter {
     type Output = EntityId;
 
-    fn validate(&self, _: &GameState) -> bool {
-        true
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        if game_state.design(&self.design).is_none() {
+            return Err(format!("Unknown ship design: {}", self.design));
+        }
+
+        let spawner = self.spawner.as_ref().map(|name| &name[..]);
+        if game_state.spawner(spawner).is_none() {
+            return match self.spawner {
+                Some(ref name) => Err(format!("Unknown spawner: {}", name)),
+                None           => Err(format!("No spawner available")),
+            };
+        }
+
+        Ok(())
     }
 
     fn execute(self, game_state: &mut GameState) -> EntityId {
-        game_state.entities.create_entity()
-            .with_body(Body {
-                position: game_state.spawner.position,
-                velocity: game_state.spawner.velocity,
-                force   : Vec2::new(0.0, 0.0),
-                mass    : 1.0,
-            })
-            .with_ship(Ship)
-            .return_id()
+        let design = game_state.design(&self.design)
+            .unwrap() // validated
+            .clone();
+
+        let (position, velocity) = {
+            let spawner = game_state
+                .spawner(self.spawner.as_ref().map(|name| &name[..]))
+                .unwrap(); // validated
+
+            (spawner.spawn_position(), spawner.velocity)
+        };
+
+        create_ship(game_state, &design, position, velocity)
+    }
+}
+
+
+/// Adds an AI-controlled probe to the game. Unlike `Enter`, this doesn't
+/// require the design to be available to players.
+pub struct SpawnProbe {
+    pub design  : ShipDesign,
+    pub probe   : Probe,
+    pub position: Vec2<f64>,
+    pub velocity: Vec2<f64>,
+}
+
+impl GameEvent for SpawnProbe {
+    type Output = EntityId;
+
+    fn validate(&self, _: &GameState) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) -> EntityId {
+        let id = create_ship(
+            game_state,
+            &self.design,
+            self.position,
+            self.velocity,
+        );
+
+        game_state.entities.update_entity(id)
+            .add_probe(self.probe);
+
+        id
+    }
+}
+
+
+/// Replaces a destroyed ship with a new one, coming from the spawner
+pub struct Respawn {
+    pub ship_id: EntityId, // the destroyed ship
+    pub design : String,
+    pub spawner: Option<String>,
+}
+
+impl GameEvent for Respawn {
+    type Output = EntityId;
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        if game_state.entities.ships.contains_key(&self.ship_id) {
+            return Err(format!("Your ship hasn't been destroyed"));
+        }
+
+        let enter = Enter {
+            design : self.design.clone(),
+            spawner: self.spawner.clone(),
+        };
+        enter.validate(game_state)
+    }
+
+    fn execute(self, game_state: &mut GameState) -> EntityId {
+        let enter = Enter {
+            design : self.design,
+            spawner: self.spawner,
+        };
+        enter.execute(game_state)
     }
 }
 
@@ -49,8 +188,8 @@ pub struct Leave {
 impl GameEvent for Leave {
     type Output = ();
 
-    fn validate(&self, _: &GameState) -> bool {
-        true
+    fn validate(&self, _: &GameState) -> Result<(), String> {
+        Ok(())
     }
 
     fn execute(self, game_state: &mut GameState) {
@@ -67,8 +206,8 @@ pub struct StartBroadcast {
 impl GameEvent for StartBroadcast {
     type Output = ();
 
-    fn validate(&self, _: &GameState) -> bool {
-        true
+    fn validate(&self, _: &GameState) -> Result<(), String> {
+        Ok(())
     }
 
     fn execute(self, game_state: &mut GameState) {
@@ -88,8 +227,8 @@ pub struct StopBroadcast {
 impl GameEvent for StopBroadcast {
     type Output = ();
 
-    fn validate(&self, _: &GameState) -> bool {
-        true
+    fn validate(&self, _: &GameState) -> Result<(), String> {
+        Ok(())
     }
 
     fn execute(self, game_state: &mut GameState) {
@@ -108,8 +247,8 @@ pub struct ScheduleManeuver {
 impl GameEvent for ScheduleManeuver {
     type Output = ();
 
-    fn validate(&self, _: &GameState) -> bool {
-        self.data.thrust >= 0.0 && self.data.thrust <= 1.0
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        validate_maneuver(game_state, self.ship_id, &self.data, None)
     }
 
     fn execute(self, game_state: &mut GameState) {
@@ -122,6 +261,57 @@ impl GameEvent for ScheduleManeuver {
 }
 
 
+/// Changes a maneuver that hasn't started yet, keeping its id
+pub struct UpdateManeuver {
+    pub ship_id    : EntityId,
+    pub maneuver_id: EntityId,
+    pub data       : ManeuverData,
+}
+
+impl GameEvent for UpdateManeuver {
+    type Output = ();
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        let maneuver =
+            match game_state.entities.maneuvers.get(&self.maneuver_id) {
+                Some(maneuver) => maneuver,
+                None           =>
+                    return Err(format!(
+                        "Maneuver not found: {}",
+                        self.maneuver_id,
+                    )),
+            };
+
+        if self.ship_id != maneuver.ship_id {
+            return Err(format!(
+                "Maneuver {} belongs to another ship",
+                self.maneuver_id,
+            ));
+        }
+        if maneuver.data.start_s <= game_state.time_s {
+            return Err(format!(
+                "Maneuver {} has already started",
+                self.maneuver_id,
+            ));
+        }
+
+        validate_maneuver(
+            game_state,
+            self.ship_id,
+            &self.data,
+            Some(self.maneuver_id),
+        )
+    }
+
+    fn execute(self, game_state: &mut GameState) {
+        game_state.entities.maneuvers
+            .get_mut(&self.maneuver_id)
+            .unwrap() // validated
+            .data = self.data;
+    }
+}
+
+
 pub struct CancelManeuver {
     pub ship_id    : EntityId,
     pub maneuver_id: EntityId,
@@ -130,14 +320,25 @@ pub struct CancelManeuver {
 impl GameEvent for CancelManeuver {
     type Output = ();
 
-    fn validate(&self, game_state: &GameState) -> bool {
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
         let maneuver =
             match game_state.entities.maneuvers.get(&self.maneuver_id) {
                 Some(maneuver) => maneuver,
-                None           => return false,
+                None           =>
+                    return Err(format!(
+                        "Maneuver not found: {}",
+                        self.maneuver_id,
+                    )),
             };
 
-        self.ship_id == maneuver.ship_id
+        if self.ship_id != maneuver.ship_id {
+            return Err(format!(
+                "Maneuver {} belongs to another ship",
+                self.maneuver_id,
+            ));
+        }
+
+        Ok(())
     }
 
     fn execute(self, game_state: &mut GameState) {
@@ -154,27 +355,726 @@ pub struct FtlJump {
 impl GameEvent for FtlJump {
     type Output = ();
 
-    fn validate(&self, _: &GameState) -> bool {
-        true
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        let drive = match game_state.entities.ftl_drives.get(&self.ship_id) {
+            Some(drive) => drive,
+            None        => return Err(format!("Ship has no FTL drive")),
+        };
+
+        if game_state.entities.dockings.contains_key(&self.ship_id) {
+            return Err(format!("Ship is docked"));
+        }
+
+        let jump_s = self.target_time_s - game_state.time_s;
+
+        // Written this way, so NaNs are rejected too
+        if !(jump_s > 0.0) {
+            return Err(format!("Can't jump into the past"));
+        }
+        if !(jump_s <= drive.max_jump_s) {
+            return Err(format!(
+                "FTL jump too long ({:.0} s, maximum is {:.0} s)",
+                jump_s, drive.max_jump_s,
+            ));
+        }
+        if game_state.time_s < drive.ready_s {
+            return Err(format!(
+                "FTL drive is recharging (ready in {:.1} s)",
+                drive.ready_s - game_state.time_s,
+            ));
+        }
+        if jump_s > drive.fuel_s {
+            return Err(format!(
+                "Not enough fuel ({:.0} s of jumps left)",
+                drive.fuel_s,
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) {
+        let impact = {
+            let mut ship =
+                match game_state.entities.bodies.get_mut(&self.ship_id) {
+                    Some(ship) => ship,
+                    None       => return,
+                };
+
+            let mut impact = None;
+
+            let mut jump_time_s = game_state.time_s;
+            'jump: while jump_time_s < self.target_time_s {
+                let delta_s  = (self.target_time_s - jump_time_s).min(100.0);
+                let previous = *ship;
+
+                for (_, planet) in &game_state.entities.planets {
+                    apply_gravity(planet, &mut ship);
+                }
+                integrate(&mut ship, delta_s);
+
+                // Steps are long, so we need to check the whole path of the
+                // step. Otherwise the ship could pass right through a small
+                // planet.
+                let mut hit: Option<f64> = None;
+                for (_, planet) in &game_state.entities.planets {
+                    let planet_hit = sweep_circle(
+                        previous.position,
+                        ship.position,
+                        planet.position,
+                        planet.radius,
+                    );
+
+                    if let Some(t) = planet_hit {
+                        hit = Some(hit.map_or(t, |hit| hit.min(t)));
+                    }
+                }
+
+                if let Some(t) = hit {
+                    // Drop out of FTL just short of the point of impact,
+                    // instead of materializing inside of the planet.
+                    let path   = ship.position - previous.position;
+                    let safe_t = (t - FTL_ABORT_DISTANCE / path.norm())
+                        .max(0.0);
+
+                    ship.position = previous.position + path * safe_t;
+                    ship.velocity = previous.velocity
+                        + (ship.velocity - previous.velocity) * safe_t;
+
+                    impact = Some(jump_time_s + delta_s * safe_t);
+                    break 'jump;
+                }
+
+                jump_time_s += delta_s;
+            }
+
+            impact
+        };
+
+        // The drive burns fuel for as long as the ship is in FTL.
+        let end_s = impact.unwrap_or(self.target_time_s);
+
+        let drive = game_state.entities.ftl_drives.get_mut(&self.ship_id);
+        if let Some(drive) = drive {
+            drive.ready_s  = game_state.time_s + drive.recharge_s;
+            drive.fuel_s  -= end_s - game_state.time_s;
+        }
+
+        if let Some(abort_time_s) = impact {
+            game_state.notifications.push((
+                self.ship_id,
+                server::Event::FtlJumpAborted(format!(
+                    "Jump aborted after {:.0} s to avoid a planetary impact",
+                    abort_time_s - game_state.time_s,
+                )),
+            ));
+        }
+    }
+}
+
+
+pub struct EjectCargo {
+    pub ship_id: EntityId,
+    pub index  : usize,
+}
+
+impl GameEvent for EjectCargo {
+    type Output = EntityId;
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        let cargo = match game_state.entities.cargo_holds.get(&self.ship_id) {
+            Some(cargo) => cargo,
+            None        => return Err(format!("Ship has no cargo hold")),
+        };
+
+        if self.index >= cargo.items.len() {
+            return Err(format!("No cargo at position {}", self.index));
+        }
+        if !game_state.entities.bodies.contains_key(&self.ship_id) {
+            return Err(format!("Ship not found: {}", self.ship_id));
+        }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) -> EntityId {
+        let item = game_state.entities.cargo_holds
+            .get_mut(&self.ship_id)
+            .unwrap() // validated
+            .items
+            .remove(self.index);
+
+        let body = {
+            let ship = game_state.entities.bodies
+                .get_mut(&self.ship_id)
+                .unwrap(); // validated
+
+            ship.mass -= item.mass;
+
+            Body {
+                position: ship.position,
+                velocity: ship.velocity,
+                force   : Vec2::new(0.0, 0.0),
+                mass    : item.mass,
+            }
+        };
+
+        game_state.entities.create_entity()
+            .with_body(body)
+            .with_signature(Signature {
+                size: body.mass,
+                heat: 0.0,
+            })
+            .with_container(Container {
+                items: vec![item],
+            })
+            .return_id()
+    }
+}
+
+
+pub struct CollectCargo {
+    pub ship_id     : EntityId,
+    pub container_id: EntityId,
+}
+
+impl GameEvent for CollectCargo {
+    type Output = ();
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        let entities = &game_state.entities;
+
+        if !is_classified(game_state, self.ship_id, self.container_id) {
+            return Err(format!("Container not found: {}", self.container_id));
+        }
+
+        let container = match entities.containers.get(&self.container_id) {
+            Some(container) => container,
+            None            =>
+                return Err(format!(
+                    "Container not found: {}",
+                    self.container_id,
+                )),
+        };
+        let cargo = match entities.cargo_holds.get(&self.ship_id) {
+            Some(cargo) => cargo,
+            None        => return Err(format!("Ship has no cargo hold")),
+        };
+
+        let ship = match entities.bodies.get(&self.ship_id) {
+            Some(body) => body,
+            None       => return Err(format!("Ship not found")),
+        };
+        let container_body = match entities.bodies.get(&self.container_id) {
+            Some(body) => body,
+            None       => return Err(format!("Container not found")),
+        };
+
+        if (ship.position - container_body.position).norm() > COLLECT_RANGE {
+            return Err(format!(
+                "Container {} is out of range",
+                self.container_id,
+            ));
+        }
+        if container.mass() > cargo.free_capacity() {
+            return Err(format!("Not enough space in cargo hold"));
+        }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) {
+        let container = game_state.entities.containers
+            .remove(&self.container_id)
+            .unwrap(); // validated
+        let mass = container.mass();
+
+        game_state.entities.cargo_holds
+            .get_mut(&self.ship_id)
+            .unwrap() // validated
+            .items
+            .extend(container.items);
+        game_state.entities.bodies
+            .get_mut(&self.ship_id)
+            .unwrap() // validated
+            .mass += mass;
+
+        game_state.to_destroy.push(self.container_id);
+    }
+}
+
+
+pub struct SalvageWreck {
+    pub ship_id : EntityId,
+    pub wreck_id: EntityId,
+}
+
+impl GameEvent for SalvageWreck {
+    type Output = ();
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        let entities = &game_state.entities;
+
+        if !is_classified(game_state, self.ship_id, self.wreck_id) {
+            return Err(format!("Wreck not found: {}", self.wreck_id));
+        }
+
+        let wreck = match entities.wrecks.get(&self.wreck_id) {
+            Some(wreck) => wreck,
+            None        =>
+                return Err(format!("Wreck not found: {}", self.wreck_id)),
+        };
+        let cargo = match entities.cargo_holds.get(&self.ship_id) {
+            Some(cargo) => cargo,
+            None        => return Err(format!("Ship has no cargo hold")),
+        };
+
+        let ship = match entities.bodies.get(&self.ship_id) {
+            Some(body) => body,
+            None       => return Err(format!("Ship not found")),
+        };
+        let wreck_body = match entities.bodies.get(&self.wreck_id) {
+            Some(body) => body,
+            None       => return Err(format!("Wreck not found")),
+        };
+
+        if (ship.position - wreck_body.position).norm() > COLLECT_RANGE {
+            return Err(format!("Wreck {} is out of range", self.wreck_id));
+        }
+
+        let fits = wreck.items
+            .iter()
+            .any(|item| item.mass <= cargo.free_capacity());
+        if !fits {
+            return Err(format!("Not enough space in cargo hold"));
+        }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) {
+        let entities = &mut game_state.entities;
+
+        let wreck = entities.wrecks
+            .get_mut(&self.wreck_id)
+            .unwrap(); // validated
+        let cargo = entities.cargo_holds
+            .get_mut(&self.ship_id)
+            .unwrap(); // validated
+
+        // Take whatever fits into the cargo hold and leave the rest.
+        let mut salvaged = 0.0;
+        let mut index    = 0;
+        while index < wreck.items.len() {
+            if wreck.items[index].mass <= cargo.free_capacity() {
+                let item = wreck.items.remove(index);
+                salvaged += item.mass;
+                cargo.items.push(item);
+            }
+            else {
+                index += 1;
+            }
+        }
+
+        if wreck.items.is_empty() {
+            wreck.stripped_s = Some(game_state.time_s);
+        }
+
+        entities.bodies
+            .get_mut(&self.ship_id)
+            .unwrap() // validated
+            .mass += salvaged;
+        entities.bodies
+            .get_mut(&self.wreck_id)
+            .unwrap() // validated
+            .mass = wreck.mass();
+    }
+}
+
+
+pub struct DispatchDrones {
+    pub ship_id : EntityId,
+    pub wreck_id: EntityId,
+}
+
+impl GameEvent for DispatchDrones {
+    type Output = ();
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        let entities = &game_state.entities;
+
+        let drone_bay = match entities.drone_bays.get(&self.ship_id) {
+            Some(drone_bay) => drone_bay,
+            None            => return Err(format!("Ship has no drone bay")),
+        };
+        if drone_bay.drones == 0 {
+            return Err(format!("No drones left"));
+        }
+        if !is_classified(game_state, self.ship_id, self.wreck_id) {
+            return Err(format!("Wreck not found: {}", self.wreck_id));
+        }
+
+        let wreck = match entities.wrecks.get(&self.wreck_id) {
+            Some(wreck) => wreck,
+            None        =>
+                return Err(format!("Wreck not found: {}", self.wreck_id)),
+        };
+        if wreck.items.is_empty() {
+            return Err(format!("Wreck {} is stripped", self.wreck_id));
+        }
+
+        let cargo = match entities.cargo_holds.get(&self.ship_id) {
+            Some(cargo) => cargo,
+            None        => return Err(format!("Ship has no cargo hold")),
+        };
+        if cargo.free_capacity() <= 0.0 {
+            return Err(format!("Not enough space in cargo hold"));
+        }
+
+        let ship = match entities.bodies.get(&self.ship_id) {
+            Some(body) => body,
+            None       => return Err(format!("Ship not found")),
+        };
+        let wreck_body = match entities.bodies.get(&self.wreck_id) {
+            Some(body) => body,
+            None       => return Err(format!("Wreck not found")),
+        };
+
+        if (ship.position - wreck_body.position).norm() > systems::DRONE_RANGE {
+            return Err(format!("Wreck {} is out of range", self.wreck_id));
+        }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) {
+        let drone_bay = game_state.entities.drone_bays
+            .get_mut(&self.ship_id)
+            .unwrap(); // validated
+
+        drone_bay.target   = Some(self.wreck_id);
+        drone_bay.salvaged = 0.0;
+    }
+}
+
+
+pub struct RecallDrones {
+    pub ship_id: EntityId,
+}
+
+impl GameEvent for RecallDrones {
+    type Output = ();
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        match game_state.entities.drone_bays.get(&self.ship_id) {
+            Some(drone_bay) if drone_bay.target.is_some() => Ok(()),
+            Some(_) => Err(format!("Drones are not deployed")),
+            None    => Err(format!("Ship has no drone bay")),
+        }
     }
 
     fn execute(self, game_state: &mut GameState) {
-        let mut ship = match game_state.entities.bodies.get_mut(&self.ship_id) {
-            Some(ship) => ship,
-            None       => return,
+        game_state.entities.drone_bays
+            .get_mut(&self.ship_id)
+            .unwrap() // validated
+            .target = None;
+    }
+}
+
+
+pub struct Dock {
+    pub ship_id   : EntityId,
+    pub station_id: EntityId,
+}
+
+impl GameEvent for Dock {
+    type Output = ();
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        let entities = &game_state.entities;
+
+        let detected = is_classified(game_state, self.ship_id, self.station_id);
+        if !detected || !entities.stations.contains_key(&self.station_id) {
+            return Err(format!("Station not found: {}", self.station_id));
+        }
+        if entities.dockings.contains_key(&self.ship_id) {
+            return Err(format!("Ship is already docked"));
+        }
+
+        let ship = match entities.bodies.get(&self.ship_id) {
+            Some(body) => body,
+            None       => return Err(format!("Ship not found")),
+        };
+        let station = match entities.bodies.get(&self.station_id) {
+            Some(body) => body,
+            None       => return Err(format!("Station not found")),
         };
 
-        let mut jump_time_s = game_state.time_s;
-        while jump_time_s < self.target_time_s {
-            let delta_s = 100.0;
+        let distance       = (ship.position - station.position).norm();
+        let relative_speed = (ship.velocity - station.velocity).norm();
 
-            for (_, planet) in &game_state.entities.planets {
-                apply_gravity(planet, &mut ship);
+        if distance > DOCKING_RANGE {
+            return Err(format!(
+                "Station {} is out of range ({:.0}, maximum is {:.0})",
+                self.station_id, distance, DOCKING_RANGE,
+            ));
+        }
+        if relative_speed > DOCKING_SPEED {
+            return Err(format!(
+                "Velocity doesn't match station ({:.1}, maximum is {:.1})",
+                relative_speed, DOCKING_SPEED,
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) {
+        // Docked ships don't maneuver.
+        for (&id, maneuver) in &game_state.entities.maneuvers {
+            if maneuver.ship_id == self.ship_id {
+                game_state.to_destroy.push(id);
             }
-            integrate(&mut ship, delta_s);
+        }
+
+        game_state.entities.update_entity(self.ship_id)
+            .add_docking(Docking {
+                station_id: self.station_id,
+            });
+
+        systems::attach_docked_ships(game_state);
+    }
+}
+
+
+pub struct Undock {
+    pub ship_id: EntityId,
+}
+
+impl GameEvent for Undock {
+    type Output = ();
 
-            jump_time_s += delta_s;
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        if !game_state.entities.dockings.contains_key(&self.ship_id) {
+            return Err(format!("Ship is not docked"));
         }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) {
+        // The ship keeps the station's velocity and moves on its own from
+        // here on.
+        game_state.entities.update_entity(self.ship_id)
+            .remove_docking();
+    }
+}
+
+
+/// Fills up the fuel of the ship's FTL drive.
+pub struct Refuel {
+    pub ship_id: EntityId,
+}
+
+impl GameEvent for Refuel {
+    type Output = ();
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        if !game_state.entities.dockings.contains_key(&self.ship_id) {
+            return Err(format!("Ship is not docked"));
+        }
+        if !game_state.entities.ftl_drives.contains_key(&self.ship_id) {
+            return Err(format!("Ship has no FTL drive"));
+        }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) {
+        let drive = game_state.entities.ftl_drives
+            .get_mut(&self.ship_id)
+            .unwrap(); // validated
+
+        drive.fuel_s = drive.max_fuel_s;
+    }
+}
+
+
+pub struct Repair {
+    pub ship_id: EntityId,
+}
+
+impl GameEvent for Repair {
+    type Output = ();
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        if !game_state.entities.dockings.contains_key(&self.ship_id) {
+            return Err(format!("Ship is not docked"));
+        }
+        if !game_state.entities.hulls.contains_key(&self.ship_id) {
+            return Err(format!("Ship has no hull"));
+        }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) {
+        let hull = game_state.entities.hulls
+            .get_mut(&self.ship_id)
+            .unwrap(); // validated
+
+        hull.integrity = hull.max_integrity;
+    }
+}
+
+
+pub struct LaunchMissile {
+    pub ship_id  : EntityId,
+    pub target_id: EntityId,
+}
+
+impl GameEvent for LaunchMissile {
+    type Output = EntityId;
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        let launcher = match game_state.entities.launchers.get(&self.ship_id) {
+            Some(launcher) => launcher,
+            None           => return Err(format!("Ship has no launcher")),
+        };
+
+        if launcher.mode == LauncherMode::Defensive {
+            return Err(format!("Launcher is in defensive mode"));
+        }
+        if launcher.missiles == 0 {
+            return Err(format!("No missiles left"));
+        }
+        if game_state.time_s < launcher.ready_s {
+            return Err(format!(
+                "Launcher is reloading (ready in {:.1} s)",
+                launcher.ready_s - game_state.time_s,
+            ));
+        }
+        if self.target_id == self.ship_id {
+            return Err(format!("Ships can't target themselves"));
+        }
+        if !game_state.entities.bodies.contains_key(&self.ship_id) {
+            return Err(format!("Ship not found: {}", self.ship_id));
+        }
+
+        // Missiles can only be launched at targets the ship's sensors have
+        // picked up. The error doesn't tell whether the target exists, so it
+        // can't be used to find ships that haven't been detected.
+        let detected = is_classified(game_state, self.ship_id, self.target_id);
+        if !detected || !game_state.entities.bodies.contains_key(&self.target_id)
+        {
+            return Err(format!("Target not found"));
+        }
+
+        // Missiles are launched from the ship's position. If the target is
+        // that close, the blast would hit the launching ship too.
+        let missile  = Missile::new(self.ship_id, self.target_id);
+        let distance = (
+            game_state.entities.bodies[&self.target_id].position
+                - game_state.entities.bodies[&self.ship_id].position
+        ).norm();
+        if distance < missile.blast_radius {
+            return Err(format!(
+                "Target is too close (minimum range: {:.0} m)",
+                missile.blast_radius,
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) -> EntityId {
+        launch_missile(
+            game_state,
+            self.ship_id,
+            Missile::new(self.ship_id, self.target_id),
+        )
+    }
+}
+
+
+pub struct SetLauncherMode {
+    pub ship_id: EntityId,
+    pub mode   : LauncherMode,
+}
+
+impl GameEvent for SetLauncherMode {
+    type Output = ();
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        if !game_state.entities.launchers.contains_key(&self.ship_id) {
+            return Err(format!("Ship has no launcher"));
+        }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) {
+        game_state.entities.launchers
+            .get_mut(&self.ship_id)
+            .unwrap() // validated
+            .mode = self.mode;
+    }
+}
+
+
+pub struct SetRadar {
+    pub ship_id: EntityId,
+    pub active : bool,
+}
+
+impl GameEvent for SetRadar {
+    type Output = ();
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        if !game_state.entities.sensors.contains_key(&self.ship_id) {
+            return Err(format!("Ship has no sensors"));
+        }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) {
+        game_state.entities.sensors
+            .get_mut(&self.ship_id)
+            .unwrap() // validated
+            .radar_on = self.active;
+    }
+}
+
+
+pub struct SetSensorFocus {
+    pub ship_id: EntityId,
+    pub focus  : Option<SensorFocus>,
+}
+
+impl GameEvent for SetSensorFocus {
+    type Output = ();
+
+    fn validate(&self, game_state: &GameState) -> Result<(), String> {
+        if !game_state.entities.sensors.contains_key(&self.ship_id) {
+            return Err(format!("Ship has no sensors"));
+        }
+        if let Some(focus) = self.focus {
+            if !(focus.width > 0.0 && focus.width <= 2.0 * PI) {
+                return Err(format!(
+                    "Focus width must be between 0 and 360 degrees"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn execute(self, game_state: &mut GameState) {
+        game_state.entities.sensors
+            .get_mut(&self.ship_id)
+            .unwrap() // validated
+            .focus = self.focus;
     }
 }
 
@@ -186,21 +1086,233 @@ pub struct Update {
 impl GameEvent for Update {
     type Output = ();
 
-    fn validate(&self, _: &GameState) -> bool {
-        true
+    fn validate(&self, _: &GameState) -> Result<(), String> {
+        Ok(())
     }
 
     fn execute(self, game_state: &mut GameState) {
         game_state.time_s = self.now_s;
 
+        for (probe_id, data) in systems::steer_probes(game_state) {
+            let maneuver = ScheduleManeuver {
+                ship_id: probe_id,
+                data   : data,
+            };
+
+            if let Err(error) = game_state.handle_event(maneuver) {
+                debug!("Probe maneuver rejected: {}", error);
+            }
+        }
+
         systems::apply_maneuvers(game_state, self.now_s);
+
+        for (ship_id, threat_id) in systems::select_interceptions(game_state) {
+            launch_missile(
+                game_state,
+                ship_id,
+                Missile::interceptor(ship_id, threat_id),
+            );
+        }
+
+        systems::guide_missiles(game_state, 0.5);
         systems::apply_gravity(game_state);
         systems::move_bodies(game_state, 0.5);
+        systems::attach_docked_ships(game_state);
+        systems::detonate_missiles(game_state);
+        systems::check_impacts(game_state);
         systems::check_collisions(game_state);
+        systems::apply_damage(game_state);
+        systems::harvest_wrecks(game_state, 0.5);
+        systems::decay_wrecks(game_state);
 
         for id in game_state.to_destroy.drain(..) {
             game_state.entities.destroy_entity(&id);
             game_state.destroyed.push(id);
         }
+
+        systems::detect_entities(game_state);
+    }
+}
+
+
+/// Creates a ship of the given design. The caller is responsible for making
+/// sure that the design is allowed.
+fn create_ship(
+    game_state: &mut GameState,
+    design    : &ShipDesign,
+    position  : Vec2<f64>,
+    velocity  : Vec2<f64>,
+) -> EntityId {
+    let mut cargo = Cargo::new(design.cargo_capacity());
+    let supplies  = CargoItem {
+        kind: CargoKind::Supplies,
+        mass: 0.25,
+    };
+    if supplies.mass <= cargo.free_capacity() {
+        cargo.items.push(supplies);
+    }
+
+    let launcher = if design.has_module(ModuleKind::Weapons) {
+        Some(Launcher::new(design.missiles()))
+    }
+    else {
+        None
+    };
+    let launcher_mass = launcher.map_or(0.0, |launcher| launcher.mass());
+
+    let id = game_state.entities.create_entity()
+        .with_body(Body {
+            position: position,
+            velocity: velocity,
+            force   : Vec2::new(0.0, 0.0),
+            mass    : design.mass() + cargo.mass() + launcher_mass,
+        })
+        .with_ship(design.ship())
+        .with_sensors(design.sensors())
+        .with_signature(design.signature())
+        .with_cargo(cargo)
+        .with_hull(Hull::new(design.integrity()))
+        .return_id();
+
+    if let Some(launcher) = launcher {
+        game_state.entities.update_entity(id)
+            .add_launcher(launcher);
+    }
+    if design.has_module(ModuleKind::DroneBay) {
+        game_state.entities.update_entity(id)
+            .add_drone_bay(DroneBay::new(design.drones()));
+    }
+    if design.has_module(ModuleKind::FtlDrive) {
+        game_state.entities.update_entity(id)
+            .add_ftl_drive(
+                FtlDrive::new(design.max_jump_s(), design.ftl_fuel_s())
+            );
+    }
+
+    id
+}
+
+
+/// Launches a missile from a ship's launcher. The caller is responsible for
+/// making sure that the launcher exists and is ready.
+fn launch_missile(
+    game_state: &mut GameState,
+    ship_id   : EntityId,
+    missile   : Missile,
+) -> EntityId {
+    let missile_mass = {
+        let launcher = game_state.entities.launchers
+            .get_mut(&ship_id)
+            .unwrap();
+
+        launcher.missiles -= 1;
+        launcher.ready_s   = game_state.time_s + launcher.reload_s;
+
+        launcher.missile_mass
+    };
+
+    let body = {
+        let ship = game_state.entities.bodies
+            .get_mut(&ship_id)
+            .unwrap();
+
+        ship.mass -= missile_mass;
+
+        Body {
+            position: ship.position,
+            velocity: ship.velocity,
+            force   : Vec2::new(0.0, 0.0),
+            mass    : missile_mass,
+        }
+    };
+
+    game_state.entities.create_entity()
+        .with_body(body)
+        .with_signature(Signature {
+            size: body.mass,
+            heat: 0.1,
+        })
+        .with_missile(missile)
+        .return_id()
+}
+
+
+/// Validates a maneuver that is about to be scheduled for a ship. If the
+/// maneuver replaces an existing one, that one is not considered for overlaps
+/// and the limit of scheduled maneuvers.
+fn validate_maneuver(
+    game_state: &GameState,
+    ship_id   : EntityId,
+    data      : &ManeuverData,
+    replaced  : Option<EntityId>,
+) -> Result<(), String> {
+    if !(0.0 <= data.thrust && data.thrust <= 1.0) {
+        return Err(format!("Thrust must be between 0.0 and 1.0"));
+    }
+    if !data.angle.is_finite() {
+        return Err(format!("Angle must be a finite number"));
+    }
+    if !data.duration_s.is_finite() || data.duration_s <= 0.0 {
+        return Err(format!("Duration must be positive"));
+    }
+
+    let earliest_start_s = game_state.time_s - MANEUVER_START_TOLERANCE_S;
+    if !data.start_s.is_finite() || data.start_s < earliest_start_s {
+        return Err(format!("Maneuver would start in the past"));
+    }
+    if game_state.entities.dockings.contains_key(&ship_id) {
+        return Err(format!("Ship is docked"));
+    }
+
+    // Otherwise, maneuvers could be used to home in on ships that the ship's
+    // sensors haven't picked up.
+    if let Some(reference) = data.orientation.reference() {
+        let detected = game_state.detections
+            .get(&ship_id)
+            .map_or(false, |detections| detections.contains_key(&reference));
+
+        if !detected && !game_state.entities.planets.contains_key(&reference) {
+            return Err(format!("Reference not detected: {}", reference));
+        }
     }
+
+    // Overlapping maneuvers are rejected, as their forces would add up in
+    // ways that are hard to predict for the player.
+    let mut queued = 0;
+    for (&id, maneuver) in &game_state.entities.maneuvers {
+        if maneuver.ship_id != ship_id || Some(id) == replaced {
+            continue;
+        }
+        queued += 1;
+
+        if data.start_s < maneuver.data.end_s()
+            && maneuver.data.start_s < data.end_s()
+        {
+            return Err(format!("Maneuver overlaps with maneuver {}", id));
+        }
+    }
+    if queued >= MAX_QUEUED_MANEUVERS {
+        return Err(format!(
+            "Too many maneuvers scheduled (maximum is {})",
+            MAX_QUEUED_MANEUVERS,
+        ));
+    }
+
+    Ok(())
+}
+
+
+/// Returns whether the ship's sensors have picked up the target well enough to
+/// tell what kind of object it is. Ships can't interact with anything else.
+fn is_classified(
+    game_state: &GameState,
+    ship_id   : EntityId,
+    target_id : EntityId,
+) -> bool {
+    game_state.detections
+        .get(&ship_id)
+        .and_then(|detections| detections.get(&target_id))
+        .map_or(false, |detection|
+            detection.quality != DetectionQuality::Unknown
+        )
 }
@@ -4,6 +4,7 @@ use std::io::prelude::*;
 use std::path::Path;
 
 use nalgebra::{
+    Norm,
     Vec1,
     Vec2,
     Rot2,
@@ -16,23 +17,58 @@ use rand::distributions::{
 };
 use rustc_serialize::json;
 
-use server::game::data::Spawner;
+use server::game::data::{
+    Probe,
+    Spawner,
+};
+use server::game::events;
 use server::game::state::GameState;
 use shared::color::Colors;
-use shared::game::data::Planet;
+use shared::game::data::{
+    Body,
+    Hull,
+    Planet,
+    Signature,
+    Station,
+};
+use shared::game::design::ShipDesign;
+use shared::game::logic::GRAVITATIONAL_CONSTANT;
+
+
+/// Distance above a celestial's surface at which probes patrol it
+const PATROL_ALTITUDE: f64 = 200.0;
+
+/// How far ships are spread out around the spawners of a random world
+const SPAWNER_JITTER: f64 = 50.0;
+
+/// Distance above a celestial's surface at which the spawners of a random world
+/// are placed. It leaves enough room below the jitter for the biggest ships, so
+/// no ship enters inside of the celestial.
+const SPAWNER_ALTITUDE: f64 = 100.0;
+
+const STATION_MASS     : f64 = 1000.0;
+const STATION_INTEGRITY: f64 = 10000.0;
 
 
 #[derive(Debug, RustcDecodable, RustcEncodable)]
 pub struct InitialState {
-    celestials: Vec<Celestial>,
-    spawner   : Spawner,
+    celestials    : Vec<Celestial>,
+    spawners      : Vec<Spawner>,
+    orbit_spawners: Vec<OrbitSpawner>,
+    designs       : Vec<ShipDesign>,
+    swarms        : Vec<Swarm>,
+    stations      : Vec<StationSite>,
 }
 
 impl InitialState {
     pub fn new() -> Self {
         InitialState {
-            celestials: Vec::new(),
-            spawner   : Spawner::new(),
+            celestials    : Vec::new(),
+            spawners      : vec![Spawner::new()],
+            orbit_spawners: Vec::new(),
+            designs       : ShipDesign::defaults(),
+            swarms        : Vec::new(),
+            stations      : Vec::new(),
         }
     }
 
@@ -65,11 +101,43 @@ impl InitialState {
 
         let mut spawner = Spawner::new();
         spawner.position =
-            planet.position + Vec2::new(0.0, planet.size + 50.0);
+            planet.position + Vec2::new(0.0, planet.size + SPAWNER_ALTITUDE);
+        spawner.jitter = SPAWNER_JITTER;
+
+        // Let players start out near the innermost moon, too.
+        let orbit_spawner = OrbitSpawner {
+            name     : "moon".to_string(),
+            celestial: 1,
+            altitude : SPAWNER_ALTITUDE,
+            jitter   : SPAWNER_JITTER,
+        };
+
+        let swarm = Swarm {
+            design       : ShipDesign::probe(),
+            size         : 3,
+            patrol_range : 100000.0,
+            pursuit_range: 10000.0,
+        };
+
+        // A station in a circular orbit around the central planet
+        let station_distance = planet.size + 1000.0;
+        let station = StationSite {
+            name    : "Anchorage".to_string(),
+            position: planet.position + Vec2::new(station_distance, 0.0),
+            velocity: Vec2::new(
+                0.0,
+                (GRAVITATIONAL_CONSTANT * planet.mass() / station_distance)
+                    .sqrt(),
+            ),
+        };
 
         InitialState {
-            celestials: celestials,
-            spawner   : spawner,
+            celestials    : celestials,
+            spawners      : vec![spawner],
+            orbit_spawners: vec![orbit_spawner],
+            designs       : ShipDesign::defaults(),
+            swarms        : vec![swarm],
+            stations      : vec![station],
         }
     }
 
@@ -113,29 +181,141 @@ impl InitialState {
         self
     }
 
+    /// Adds a spawner, replacing any existing spawner of the same name.
     pub fn with_spawner(mut self, spawner: Spawner) -> Self {
-        self.spawner = spawner;
+        self.remove_spawner(&spawner.name);
+        self.spawners.push(spawner);
         self
     }
 
-    pub fn apply(&self, game_state: &mut GameState) {
-        let entities = &mut game_state.entities;
+    /// Adds a spawner that places ships in orbit around a celestial,
+    /// replacing any existing spawner of the same name.
+    pub fn with_orbit_spawner(mut self, spawner: OrbitSpawner) -> Self {
+        self.remove_spawner(&spawner.name);
+        self.orbit_spawners.push(spawner);
+        self
+    }
 
-        for celestial in &self.celestials {
-            // Let's say mass is just proportional to volume.
-            let size_cubed = celestial.size * celestial.size * celestial.size;
-            let mass       = size_cubed * 4.0 / 3.0 * PI * 50000.0;
+    /// Adds a ship design, replacing any existing design of the same name.
+    pub fn with_design(mut self, design: ShipDesign) -> Self {
+        self.designs.retain(|existing| existing.name != design.name);
+        self.designs.push(design);
+        self
+    }
+
+    pub fn with_swarm(mut self, swarm: Swarm) -> Self {
+        self.swarms.push(swarm);
+        self
+    }
 
-            entities.create_entity()
+    pub fn with_station(mut self, station: StationSite) -> Self {
+        self.stations.push(station);
+        self
+    }
+
+    pub fn apply(&self, game_state: &mut GameState) {
+        for celestial in &self.celestials {
+            game_state.entities.create_entity()
                 .with_planet(Planet {
                     position: celestial.position,
                     radius  : celestial.size,
-                    mass    : mass,
+                    mass    : celestial.mass(),
                     color   : Colors::random(),
                 });
         }
 
-        game_state.spawner = self.spawner;
+        for station in &self.stations {
+            game_state.entities.create_entity()
+                .with_body(Body {
+                    position: station.position,
+                    velocity: station.velocity,
+                    force   : Vec2::new(0.0, 0.0),
+                    mass    : STATION_MASS,
+                })
+                .with_station(Station {
+                    name: station.name.clone(),
+                })
+                .with_hull(Hull::new(STATION_INTEGRITY))
+                .with_signature(Signature {
+                    size: STATION_MASS,
+                    heat: 1.0,
+                });
+        }
+
+        game_state.spawners = self.spawners.clone();
+        for spawner in &self.orbit_spawners {
+            let celestial = match self.celestials.get(spawner.celestial) {
+                Some(celestial) => celestial,
+                None            =>
+                    panic!(
+                        "Spawner {} orbits unknown celestial {}",
+                        spawner.name, spawner.celestial,
+                    ),
+            };
+
+            game_state.spawners.push(spawner.resolve(celestial));
+        }
+        game_state.designs = self.designs.clone();
+
+        for swarm in &self.swarms {
+            let patrol = self.patrol_for(swarm);
+
+            for i in 0 .. swarm.size as usize {
+                let mut probe = Probe::new(patrol.clone(), swarm.pursuit_range);
+
+                // Spread the swarm out over the waypoints. Without any
+                // waypoints, probes start out where ships enter.
+                let (position, velocity) = if !patrol.is_empty() {
+                    probe.waypoint = i % patrol.len();
+                    (patrol[probe.waypoint], Vec2::new(0.0, 0.0))
+                }
+                else {
+                    match game_state.spawner(None) {
+                        Some(spawner) =>
+                            (spawner.spawn_position(), spawner.velocity),
+                        None =>
+                            panic!("No spawner available for probes"),
+                    }
+                };
+
+                let event = events::SpawnProbe {
+                    design  : swarm.design.clone(),
+                    probe   : probe,
+                    position: position,
+                    velocity: velocity,
+                };
+
+                if let Err(error) = game_state.handle_event(event) {
+                    panic!("Error spawning probe: {}", error);
+                }
+            }
+        }
+    }
+
+    fn remove_spawner(&mut self, name: &str) {
+        self.spawners.retain(|existing| existing.name != name);
+        self.orbit_spawners.retain(|existing| existing.name != name);
+    }
+
+    /// Returns the waypoints a swarm patrols. Those are positions just above
+    /// all celestials within the swarm's patrol range. The range is measured
+    /// from the first celestial, which is the system's central body.
+    fn patrol_for(&self, swarm: &Swarm) -> Vec<Vec2<f64>> {
+        let center = match self.celestials.first() {
+            Some(celestial) => celestial.position,
+            None            => return Vec::new(),
+        };
+
+        self.celestials
+            .iter()
+            .filter(|celestial|
+                (celestial.position - center).norm() <= swarm.patrol_range
+            )
+            .map(|celestial|
+                celestial.position
+                    + Vec2::new(0.0, celestial.size + PATROL_ALTITUDE)
+            )
+            .collect()
     }
 }
 
@@ -145,3 +325,58 @@ pub struct Celestial {
     pub position: Vec2<f64>,
     pub size    : f64,
 }
+
+impl Celestial {
+    pub fn mass(&self) -> f64 {
+        // Let's say mass is just proportional to volume.
+        let size_cubed = self.size * self.size * self.size;
+        size_cubed * 4.0 / 3.0 * PI * 50000.0
+    }
+}
+
+
+/// A group of probes that share a design and patrol the same celestials. The
+/// design doesn't need to be among the designs that players can choose.
+#[derive(Clone, Debug, RustcDecodable, RustcEncodable)]
+pub struct Swarm {
+    pub design       : ShipDesign,
+    pub size         : u32,
+    pub patrol_range : f64,
+    pub pursuit_range: f64,
+}
+
+
+/// A station that is placed in the world at startup. Stations are affected by
+/// gravity, so they need the right velocity to stay in orbit.
+#[derive(Clone, Debug, RustcDecodable, RustcEncodable)]
+pub struct StationSite {
+    pub name    : String,
+    pub position: Vec2<f64>,
+    pub velocity: Vec2<f64>,
+}
+
+
+/// A spawner that places ships in a circular orbit around a celestial. The
+/// celestial is identified by its index in the list of celestials.
+#[derive(Clone, Debug, RustcDecodable, RustcEncodable)]
+pub struct OrbitSpawner {
+    pub name     : String,
+    pub celestial: usize,
+    pub altitude : f64, // above the celestial's surface
+    pub jitter   : f64,
+}
+
+impl OrbitSpawner {
+    pub fn resolve(&self, celestial: &Celestial) -> Spawner {
+        let distance = celestial.size + self.altitude;
+        let speed    =
+            (GRAVITATIONAL_CONSTANT * celestial.mass() / distance).sqrt();
+
+        Spawner {
+            name    : self.name.clone(),
+            position: celestial.position + Vec2::new(0.0, distance),
+            velocity: Vec2::new(speed, 0.0),
+            jitter  : self.jitter,
+        }
+    }
+}
@@ -1,15 +1,43 @@
+use std::collections::HashMap;
 use std::vec::Drain;
 
+use nalgebra::{
+    Norm,
+    Vec2,
+};
+
 use server::game::data::Spawner;
 use server::game::entities::Entities;
-use shared::game::data::EntityId;
-use shared::protocol::server::Entity;
+use shared::game::data::{
+    Classification,
+    Contact,
+    DamageSource,
+    Detection,
+    DetectionQuality,
+    EntityId,
+};
+use shared::game::design::ShipDesign;
+use shared::protocol::server::{
+    Entity,
+    Event,
+};
+
+
+/// Error of the estimated position of contacts, relative to their distance,
+/// for the weakest possible detection
+const POSITION_ERROR: f64 = 0.1;
+
+/// Error of the estimated velocity of contacts is the position error, divided
+/// by this
+const VELOCITY_ERROR_S: f64 = 60.0;
 
 
 pub trait GameEvent {
     type Output;
 
-    fn validate(&self, game_state: &GameState) -> bool;
+    /// Returns `Err` with a reason that can be shown to the player, if the
+    /// event must not be executed.
+    fn validate(&self, game_state: &GameState) -> Result<(), String>;
     fn execute(self, game_state: &mut GameState) -> Self::Output;
 }
 
@@ -17,12 +45,27 @@ pub trait GameEvent {
 #[derive(Debug)]
 pub struct GameState {
     pub entities: Entities,
-    pub spawner : Spawner,
+    pub spawners: Vec<Spawner>,
+    pub designs : Vec<ShipDesign>,
     pub time_s  : f64,
 
     pub to_destroy: Vec<EntityId>,
     pub destroyed : Vec<EntityId>,
 
+    /// Where each body was before it was moved during the current update.
+    /// Used to check for collisions along the whole path of the body.
+    pub previous_positions: HashMap<EntityId, Vec2<f64>>,
+
+    /// Damage that has been dealt during the current update, but hasn't been
+    /// applied yet.
+    pub damage: Vec<(EntityId, f64, DamageSource)>,
+
+    /// Events that are addressed to the owner of a specific ship.
+    pub notifications: Vec<(EntityId, Event)>,
+
+    /// What the sensors of each ship currently detect, by observing ship.
+    pub detections: HashMap<EntityId, HashMap<EntityId, Detection>>,
+
     export_buffer: Vec<Entity>,
 }
 
@@ -30,24 +73,42 @@ impl GameState {
     pub fn new(initial_time_s: f64) -> GameState {
         GameState {
             entities: Entities::new(),
-            spawner : Spawner::new(),
+            spawners: vec![Spawner::new()],
+            designs : ShipDesign::defaults(),
             time_s  : initial_time_s,
 
             to_destroy: Vec::new(),
             destroyed : Vec::new(),
 
+            previous_positions: HashMap::new(),
+
+            damage: Vec::new(),
+
+            notifications: Vec::new(),
+
+            detections: HashMap::new(),
+
             export_buffer: Vec::new(),
         }
     }
 
-    pub fn handle_event<E>(&mut self, event: E) -> Result<E::Output, ()>
+    pub fn handle_event<E>(&mut self, event: E) -> Result<E::Output, String>
         where E: GameEvent
     {
-        if event.validate(self) {
-            Ok(event.execute(self))
-        }
-        else {
-            Err(())
+        try!(event.validate(self));
+        Ok(event.execute(self))
+    }
+
+    pub fn design(&self, name: &str) -> Option<&ShipDesign> {
+        self.designs.iter().find(|design| design.name == name)
+    }
+
+    /// Returns the spawner of the given name, or the first spawner, if no name
+    /// is given.
+    pub fn spawner(&self, name: Option<&str>) -> Option<&Spawner> {
+        match name {
+            Some(name) => self.spawners.iter().find(|s| s.name == name),
+            None       => self.spawners.first(),
         }
     }
 
@@ -63,36 +124,185 @@ impl GameState {
                 .map(|broadcast|
                      broadcast.clone()
                 );
+            let cargo = self.entities.cargo_holds
+                .get(id)
+                .map(|cargo|
+                    cargo.clone()
+                );
+            let container = self.entities.containers
+                .get(id)
+                .map(|container|
+                    container.clone()
+                );
+            let docking = self.entities.dockings
+                .get(id)
+                .map(|docking|
+                    *docking
+                );
+            let drone_bay = self.entities.drone_bays
+                .get(id)
+                .map(|drone_bay|
+                    *drone_bay
+                );
+            let hull = self.entities.hulls
+                .get(id)
+                .map(|hull|
+                    *hull
+                );
+            let launcher = self.entities.launchers
+                .get(id)
+                .map(|launcher|
+                    *launcher
+                );
             let maneuver = self.entities.maneuvers
                 .get(id)
                 .map(|maneuver|
                     *maneuver
                 );
+            let missile = self.entities.missiles
+                .get(id)
+                .map(|missile|
+                    *missile
+                );
             let planet = self.entities.planets
                 .get(id)
                 .map(|planet|
                     *planet
                 );
+            let sensors = self.entities.sensors
+                .get(id)
+                .map(|sensors|
+                    *sensors
+                );
             let ship = self.entities.ships
                 .get(id)
                 .map(|ship|
                     *ship
                 );
+            let station = self.entities.stations
+                .get(id)
+                .map(|station|
+                    station.clone()
+                );
+            let wreck = self.entities.wrecks
+                .get(id)
+                .map(|wreck|
+                    wreck.clone()
+                );
 
             self.export_buffer.push(Entity {
                 id: *id,
 
                 body     : body,
                 broadcast: broadcast,
+                cargo    : cargo,
+                container: container,
+                docking  : docking,
+                drone_bay: drone_bay,
+                hull     : hull,
+                launcher : launcher,
                 maneuver : maneuver,
+                missile  : missile,
                 planet   : planet,
+                sensors  : sensors,
                 ship     : ship,
+                station  : station,
+                wreck    : wreck,
+
+                detection: None,
             });
         }
 
         self.export_buffer.drain(..)
     }
 
+    /// Returns the update that tells the owner of the given ship about an
+    /// entity, or `None`, if the ship doesn't know about the entity at all.
+    pub fn update_for(&self, observer_id: EntityId, entity: &Entity)
+        -> Option<Event>
+    {
+        if entity.id == observer_id {
+            return Some(Event::UpdateEntity(entity.clone()));
+        }
+        if let Some(maneuver) = entity.maneuver {
+            // Maneuvers are private to the ship that performs them.
+            if maneuver.ship_id == observer_id {
+                return Some(Event::UpdateEntity(entity.clone()));
+            }
+            else {
+                return None;
+            }
+        }
+
+        let body = match entity.body {
+            Some(body) => body,
+
+            // Whatever has no body, like a planet, can't be hidden.
+            None => return Some(Event::UpdateEntity(entity.clone())),
+        };
+
+        let detection = self.detections
+            .get(&observer_id)
+            .and_then(|detections| detections.get(&entity.id));
+        let detection = match detection {
+            Some(detection) => *detection,
+            None            => return None,
+        };
+
+        if detection.quality == DetectionQuality::Identified {
+            let mut entity = entity.clone();
+            entity.detection = Some(detection);
+
+            // What a ship carries and the state of its equipment are only
+            // known to its owner.
+            entity.cargo     = None;
+            entity.drone_bay = None;
+            entity.launcher  = None;
+            entity.sensors   = None;
+            return Some(Event::UpdateEntity(entity));
+        }
+
+        // The ship doesn't know what it's looking at, so the client only gets
+        // a rough estimate.
+        let classification = if detection.quality == DetectionQuality::Unknown {
+            Classification::Unknown
+        }
+        else if entity.ship.is_some() {
+            Classification::Ship
+        }
+        else if entity.missile.is_some() {
+            Classification::Missile
+        }
+        else if entity.container.is_some() {
+            Classification::Container
+        }
+        else if entity.wreck.is_some() {
+            Classification::Wreck
+        }
+        else if entity.station.is_some() {
+            Classification::Station
+        }
+        else {
+            Classification::Unknown
+        };
+
+        let distance = match self.entities.bodies.get(&observer_id) {
+            Some(observer) => (body.position - observer.position).norm(),
+            None           => return None,
+        };
+        let uncertainty = distance * POSITION_ERROR / detection.strength;
+
+        let contact = Contact {
+            classification: classification,
+            position      : quantize(body.position, uncertainty),
+            velocity      :
+                quantize(body.velocity, uncertainty / VELOCITY_ERROR_S),
+            uncertainty   : uncertainty,
+        };
+
+        Some(Event::UpdateContact(entity.id, contact))
+    }
+
     pub fn get_entities(&self) -> &Entities {
         &self.entities
     }
@@ -100,4 +310,23 @@ impl GameState {
     pub fn destroyed_entities(&mut self) -> Drain<EntityId> {
         self.destroyed.drain(..)
     }
+
+    pub fn notifications(&mut self) -> Drain<(EntityId, Event)> {
+        self.notifications.drain(..)
+    }
+}
+
+
+/// Rounds a vector to a multiple of `step`. This is used to estimate the
+/// position of contacts. It hides the real position without making the
+/// estimate jump around.
+fn quantize(v: Vec2<f64>, step: f64) -> Vec2<f64> {
+    if step <= 0.0 {
+        return v;
+    }
+
+    Vec2::new(
+        (v.x / step).round() * step,
+        (v.y / step).round() * step,
+    )
 }
@@ -1,4 +1,8 @@
+use std::collections::HashMap;
+use std::f64;
+
 use nalgebra::{
+    Dot,
     Norm,
     Rot2,
     Rotate,
@@ -7,34 +11,437 @@ use nalgebra::{
 };
 
 use server::game::state::GameState;
+use shared::game::data::{
+    Body,
+    CargoItem,
+    CargoKind,
+    DamageSource,
+    Detection,
+    DetectionQuality,
+    EntityId,
+    LauncherMode,
+    ManeuverData,
+    Missile,
+    Orientation,
+    Planet,
+    Signature,
+    Wreck,
+};
 use shared::game::logic;
+use shared::physics::SpatialIndex;
+use shared::util::angle_of;
+use shared::protocol::server::Event;
+
+
+/// Size of bodies that aren't ships, for the purpose of impacts
+const BODY_RADIUS: f64 = 10.0;
+
+/// Cell size of the spatial indices that are used to find nearby bodies
+const INDEX_CELL_SIZE: f64 = 100.0;
+
+/// Ships that touch slower than this are not considered to impact
+const MIN_IMPACT_SPEED: f64 = 1.0;
+
+/// Other bodies need to be at least this fast to damage a ship
+const MIN_DEBRIS_SPEED: f64 = 5.0;
+
+const DAMAGE_PER_ENERGY: f64 = 10.0;
+
+/// Wrecks and containers break apart, if a single impact deals this much
+/// damage. They don't have a hull, so anything less doesn't harm them.
+const DEBRIS_DAMAGE_THRESHOLD: f64 = 100.0;
+
+/// Part of a destroyed ship's mass that can be salvaged as scrap
+const SCRAP_FRACTION: f64 = 0.5;
+
+/// Maximum distance between a ship and a wreck its drones harvest
+pub const DRONE_RANGE: f64 = 1000.0;
+
+/// Time between two decisions of a probe
+const PROBE_DECISION_S: f64 = 5.0;
+
+/// Speed at which probes travel
+const PROBE_SPEED: f64 = 20.0;
+
+/// Probes that get closer to each other than this try to spread out again
+const PROBE_SEPARATION: f64 = 500.0;
+
+/// Probes consider a waypoint reached, once they're this close
+const WAYPOINT_RADIUS: f64 = 100.0;
+
+/// Time a wreck lasts after everything has been salvaged from it
+const WRECK_DECAY_S: f64 = 60.0;
+
+/// Heat emitted per unit of thrust. Thrusting ships are easy to see.
+const HEAT_PER_THRUST: f64 = 10.0;
+
+/// Emission of an active radar. Anyone can see a ship that uses its radar.
+const RADAR_EMISSION: f64 = 100.0;
+
+/// Detections at least this strong are good enough to tell what kind of object
+/// has been detected.
+const CLASSIFICATION_STRENGTH: f64 = 1.5;
+
+/// Detections at least this strong are good enough to identify the object.
+const IDENTIFICATION_STRENGTH: f64 = 2.0;
 
 
 pub fn apply_maneuvers(game_state: &mut GameState, now_s: f64) {
     for (&id, maneuver) in &mut game_state.entities.maneuvers {
         if now_s >= maneuver.data.start_s {
-            let rotation = Rot2::new(Vec1::new(maneuver.data.angle));
+            let max_thrust = game_state.entities.ships
+                .get(&maneuver.ship_id)
+                .map_or(0.0, |ship| ship.thrust);
+
+            let angle = {
+                let bodies = &game_state.entities.bodies;
+                match bodies.get(&maneuver.ship_id) {
+                    Some(body) => thrust_angle(
+                        bodies,
+                        &game_state.entities.planets,
+                        game_state.detections.get(&maneuver.ship_id),
+                        body,
+                        &maneuver.data,
+                    ),
+                    None => maneuver.data.angle,
+                }
+            };
+
+            let rotation = Rot2::new(Vec1::new(angle));
             let force    = rotation.rotate(&Vec2::new(1.0, 0.0));
-            let force    = force * maneuver.data.thrust;
+            let force    = force * maneuver.data.thrust * max_thrust;
 
             match game_state.entities.bodies.get_mut(&maneuver.ship_id) {
                 Some(body) =>
                     body.force = body.force + force,
 
                 // The ship might not exist due to timing issues (it could
-                // have been destroyed while the message was in flight). If
-                // this happens too often, it might also be the symptom of a
-                // bug.
-                None => debug!("Ship not found: {}", maneuver.ship_id),
+                // have been destroyed while the message was in flight), or
+                // it has been destroyed during the maneuver. If this happens
+                // too often, it might also be the symptom of a bug.
+                None => {
+                    debug!("Ship not found: {}", maneuver.ship_id);
+                    game_state.to_destroy.push(id);
+                    continue;
+                },
             }
         }
 
-        if now_s >= maneuver.data.start_s + maneuver.data.duration_s {
+        if now_s >= maneuver.data.end_s() {
             game_state.to_destroy.push(id);
         }
     }
 }
 
+/// Returns the direction of a maneuver's thrust, according to its orientation
+fn thrust_angle(
+    bodies    : &HashMap<EntityId, Body>,
+    planets   : &HashMap<EntityId, Planet>,
+    detections: Option<&HashMap<EntityId, Detection>>,
+    ship      : &Body,
+    data      : &ManeuverData,
+) -> f64 {
+    // Ships can only orient themselves relative to what their sensors pick
+    // up. Planets can always be seen.
+    let body_of = |id: EntityId| {
+        detections
+            .and_then(|detections| detections.get(&id))
+            .and_then(|_| bodies.get(&id))
+    };
+    let position_of = |id: EntityId| {
+        body_of(id)
+            .map(|body| body.position)
+            .or_else(|| planets.get(&id).map(|planet| planet.position))
+    };
+    let velocity_of = |id: EntityId| {
+        body_of(id)
+            .map(|body| body.velocity)
+            .or_else(|| planets.get(&id).map(|_| Vec2::new(0.0, 0.0)))
+    };
+
+    let direction = match data.orientation {
+        Orientation::Fixed =>
+            None,
+        Orientation::Prograde =>
+            Some(ship.velocity),
+        Orientation::Retrograde =>
+            Some(-ship.velocity),
+        Orientation::RadialIn(id) =>
+            position_of(id).map(|position| position - ship.position),
+        Orientation::RadialOut(id) =>
+            position_of(id).map(|position| ship.position - position),
+        Orientation::Normal(id) =>
+            position_of(id).map(|position| {
+                let radial = ship.position - position;
+                Vec2::new(-radial.y, radial.x)
+            }),
+        Orientation::Target(id) =>
+            position_of(id).map(|position| position - ship.position),
+        Orientation::MatchVelocity(id) =>
+            velocity_of(id).map(|velocity| velocity - ship.velocity),
+    };
+
+    match direction {
+        Some(direction) if direction.norm() > 0.0 => angle_of(direction),
+        _                                         => data.angle,
+    }
+}
+
+/// Returns the inbound missiles that ships with defensive launchers are going
+/// to shoot down, as pairs of ship and missile ids.
+pub fn select_interceptions(game_state: &GameState)
+    -> Vec<(EntityId, EntityId)>
+{
+    let entities = &game_state.entities;
+
+    let mut interceptions = Vec::new();
+
+    for (&ship_id, launcher) in &entities.launchers {
+        if launcher.mode != LauncherMode::Defensive
+            || launcher.missiles == 0
+            || game_state.time_s < launcher.ready_s
+        {
+            continue;
+        }
+
+        let ship = match entities.bodies.get(&ship_id) {
+            Some(ship) => ship,
+            None       => continue,
+        };
+
+        // The most threatening missile is the one that's going to hit first.
+        let mut most_threatening: Option<(EntityId, f64)> = None;
+
+        for (&threat_id, threat) in &entities.missiles {
+            if threat.target != ship_id {
+                continue;
+            }
+
+            // Don't waste interceptors on missiles that are already being
+            // taken care of.
+            let engaged = entities.missiles.values().any(|missile|
+                missile.owner == ship_id && missile.target == threat_id
+            );
+            if engaged {
+                continue;
+            }
+
+            let body = match entities.bodies.get(&threat_id) {
+                Some(body) => body,
+                None       => continue,
+            };
+
+            let to_ship  = ship.position - body.position;
+            let distance = to_ship.norm();
+            if distance < Missile::interceptor(ship_id, threat_id).blast_radius
+            {
+                // An interceptor would detonate right next to the ship and
+                // damage it, on top of the damage the threat is going to do.
+                continue;
+            }
+
+            let closing_speed =
+                (body.velocity - ship.velocity).dot(&to_ship) / distance;
+
+            if distance > launcher.defense_range || closing_speed <= 0.0 {
+                continue;
+            }
+
+            let time_to_impact = distance / closing_speed;
+
+            let is_more_threatening = match most_threatening {
+                Some((_, time_s)) => time_to_impact < time_s,
+                None              => true,
+            };
+            if is_more_threatening {
+                most_threatening = Some((threat_id, time_to_impact));
+            }
+        }
+
+        if let Some((threat_id, _)) = most_threatening {
+            interceptions.push((ship_id, threat_id));
+        }
+    }
+
+    interceptions
+}
+
+/// Returns the maneuvers that probes are going to schedule, as pairs of probe
+/// id and maneuver. Probes follow a few simple steering rules: They pursue the
+/// nearest ship they detect, patrol their waypoints if there is none, and keep
+/// some distance to each other.
+pub fn steer_probes(game_state: &mut GameState)
+    -> Vec<(EntityId, ManeuverData)>
+{
+    let now_s    = game_state.time_s;
+    let entities = &mut game_state.entities;
+
+    let probe_positions: Vec<_> = entities.probes
+        .keys()
+        .filter_map(|id|
+            entities.bodies.get(id).map(|body| (*id, body.position))
+        )
+        .collect();
+
+    let mut maneuvers = Vec::new();
+
+    for (&id, probe) in &mut entities.probes {
+        if now_s < probe.next_decision_s {
+            continue;
+        }
+        probe.next_decision_s = now_s + PROBE_DECISION_S;
+
+        let body = match entities.bodies.get(&id) {
+            Some(body) => *body,
+            None       => continue,
+        };
+        let max_thrust = match entities.ships.get(&id) {
+            Some(ship) => ship.thrust,
+            None       => continue,
+        };
+
+        // Pursue the nearest ship that isn't a probe itself.
+        probe.target = None;
+        let mut nearest = probe.pursuit_range;
+        if let Some(detections) = game_state.detections.get(&id) {
+            for (&target_id, detection) in detections {
+                let is_probe = probe_positions
+                    .iter()
+                    .any(|&(probe_id, _)| probe_id == target_id);
+
+                if detection.quality == DetectionQuality::Unknown
+                    || !entities.ships.contains_key(&target_id)
+                    || is_probe
+                {
+                    continue;
+                }
+
+                let target = match entities.bodies.get(&target_id) {
+                    Some(target) => target,
+                    None         => continue,
+                };
+
+                let distance = (target.position - body.position).norm();
+                if distance <= nearest {
+                    nearest      = distance;
+                    probe.target = Some(target_id);
+                }
+            }
+        }
+
+        let destination = match probe.target {
+            Some(target_id) =>
+                Some(entities.bodies[&target_id].position),
+            None if !probe.patrol.is_empty() => {
+                let waypoint = probe.patrol[probe.waypoint];
+                if (waypoint - body.position).norm() <= WAYPOINT_RADIUS {
+                    probe.waypoint = (probe.waypoint + 1) % probe.patrol.len();
+                }
+                Some(probe.patrol[probe.waypoint])
+            },
+            None =>
+                None,
+        };
+
+        // Head for the destination, slowing down on approach. Without a
+        // destination, the probe just stops.
+        let mut desired_velocity = match destination {
+            Some(destination) => {
+                let to_destination = destination - body.position;
+                let distance       = to_destination.norm();
+
+                if distance > 0.0 {
+                    let speed = PROBE_SPEED.min(distance / PROBE_DECISION_S);
+                    to_destination / distance * speed
+                }
+                else {
+                    Vec2::new(0.0, 0.0)
+                }
+            },
+            None =>
+                Vec2::new(0.0, 0.0),
+        };
+
+        for &(other_id, position) in &probe_positions {
+            let away     = body.position - position;
+            let distance = away.norm();
+
+            if other_id == id || distance == 0.0 || distance > PROBE_SEPARATION
+            {
+                continue;
+            }
+
+            let urgency = 1.0 - distance / PROBE_SEPARATION;
+            desired_velocity =
+                desired_velocity + away / distance * PROBE_SPEED * urgency;
+        }
+
+        let correction   = desired_velocity - body.velocity;
+        let acceleration = max_thrust / body.mass;
+        if correction.norm() < 1.0 || acceleration <= 0.0 {
+            continue;
+        }
+
+        maneuvers.push((id, ManeuverData {
+            start_s    : now_s,
+            duration_s :
+                (correction.norm() / acceleration).min(PROBE_DECISION_S),
+            angle      : angle_of(correction),
+            thrust     : 1.0,
+            orientation: Orientation::Fixed,
+        }));
+    }
+
+    maneuvers
+}
+
+pub fn guide_missiles(game_state: &mut GameState, delta_t_s: f64) {
+    let mut forces = Vec::new();
+
+    for (&id, missile) in &mut game_state.entities.missiles {
+        if missile.fuel_s <= 0.0 {
+            continue;
+        }
+
+        let body = match game_state.entities.bodies.get(&id) {
+            Some(body) => body,
+            None       => continue,
+        };
+        // If the target is gone, the missile will be removed when checking
+        // for detonations.
+        let target = match game_state.entities.bodies.get(&missile.target) {
+            Some(target) => target,
+            None         => continue,
+        };
+
+        let to_target = target.position - body.position;
+        if to_target.sqnorm() == 0.0 {
+            // Nothing to steer towards. The missile is going to detonate
+            // anyway.
+            continue;
+        }
+
+        // Simple pursuit: Steer such that the velocity relative to the target
+        // points straight at it. This compensates for the target's own
+        // movement without having to predict its path.
+        let line_of_sight     = to_target.normalize();
+        let relative_velocity = body.velocity - target.velocity;
+        let desired_velocity  =
+            line_of_sight * (relative_velocity.norm() + 1.0);
+        let direction = (desired_velocity - relative_velocity).normalize();
+
+        forces.push((id, direction * missile.thrust));
+
+        missile.fuel_s -= delta_t_s;
+    }
+
+    for (id, force) in forces {
+        if let Some(body) = game_state.entities.bodies.get_mut(&id) {
+            body.force = body.force + force;
+        }
+    }
+}
+
 pub fn apply_gravity(game_state: &mut GameState) {
     for (_, planet) in &game_state.entities.planets {
         for (_, body) in &mut game_state.entities.bodies {
@@ -44,19 +451,622 @@ pub fn apply_gravity(game_state: &mut GameState) {
 }
 
 pub fn move_bodies(game_state: &mut GameState, delta_t_s: f64) {
-     for (_, body) in &mut game_state.entities.bodies {
+    game_state.previous_positions.clear();
+
+    for (&id, body) in &mut game_state.entities.bodies {
+        game_state.previous_positions.insert(id, body.position);
         logic::integrate(body, delta_t_s);
     }
 }
 
+/// Docked ships move with their station. If the station is gone, they are set
+/// free.
+pub fn attach_docked_ships(game_state: &mut GameState) {
+    let entities = &mut game_state.entities;
+
+    let mut undocked = Vec::new();
+
+    for (&ship_id, docking) in &entities.dockings {
+        let station = match entities.bodies.get(&docking.station_id) {
+            Some(station) => *station,
+            None          => {
+                undocked.push(ship_id);
+                continue;
+            },
+        };
+
+        if let Some(ship) = entities.bodies.get_mut(&ship_id) {
+            ship.position = station.position;
+            ship.velocity = station.velocity;
+        }
+    }
+
+    for ship_id in undocked {
+        entities.update_entity(ship_id)
+            .remove_docking();
+    }
+}
+
 pub fn check_collisions(game_state: &mut GameState) {
     for (&body_id, body) in &game_state.entities.bodies {
-        for (_, planet) in &game_state.entities.planets {
-            let squared_radius = planet.radius * planet.radius;
+        let previous = previous_position(game_state, body_id, body);
+
+        for (&planet_id, planet) in &game_state.entities.planets {
+            let hit = logic::sweep_circle(
+                previous,
+                body.position,
+                planet.position,
+                planet.radius,
+            );
+
+            if hit.is_some() {
+                game_state.damage.push((
+                    body_id,
+                    f64::INFINITY,
+                    DamageSource::PlanetImpact(planet_id),
+                ));
+            }
+        }
+    }
+}
+
+pub fn check_impacts(game_state: &mut GameState) {
+    // Missiles are excluded, as they are handled by `detonate_missiles`.
+    let candidates: HashMap<_, _> = game_state.entities.bodies
+        .iter()
+        .filter(|&(id, _)| !game_state.entities.missiles.contains_key(id))
+        .map(|(&id, body)| {
+            let radius = game_state.entities.ships
+                .get(&id)
+                .map_or(BODY_RADIUS, |ship| ship.radius);
+
+            let candidate = ImpactCandidate {
+                id      : id,
+                body    : *body,
+                previous: previous_position(game_state, id, body),
+                radius  : radius,
+            };
+
+            (id, candidate)
+        })
+        .collect();
+
+    // Broad phase: Each body is bounded by a circle that contains its whole
+    // path during this update. Two bodies can only touch, if their bounding
+    // circles overlap. The body with the bigger circle is responsible for
+    // finding the other one.
+    let mut index = SpatialIndex::new(INDEX_CELL_SIZE);
+    for (&id, candidate) in &candidates {
+        index.insert(id, candidate.center());
+    }
+
+    let entities = &mut game_state.entities;
+
+    let mut impacts = Vec::new();
+
+    for (&id_a, a) in &candidates {
+        for id_b in index.neighbours(a.center(), a.reach() * 2.0) {
+            let b = &candidates[&id_b];
+
+            let is_responsible = b.reach() < a.reach()
+                || (b.reach() == a.reach() && id_b > id_a);
+            if !is_responsible {
+                continue;
+            }
+            if (b.center() - a.center()).norm() > a.reach() + b.reach() {
+                continue;
+            }
+
+            // Narrow phase: Check whether the two bodies actually touch.
+
+            // Only damageable entities can be hit.
+            let hull_a = entities.hulls.contains_key(&a.id);
+            let hull_b = entities.hulls.contains_key(&b.id);
+            if !hull_a && !hull_b {
+                continue;
+            }
+
+            let a_to_b            = b.body.position - a.body.position;
+            let relative_velocity = a.body.velocity - b.body.velocity;
+
+            // Follow b's path relative to a, to find out whether they touched
+            // at any time during the update.
+            let hit = logic::sweep_circle(
+                b.previous - a.previous,
+                a_to_b,
+                Vec2::new(0.0, 0.0),
+                a.radius + b.radius,
+            );
+
+            match hit {
+                // The bodies started touching during this update.
+                Some(t) if t > 0.0 => (),
+
+                // The bodies have already been touching. If they're moving
+                // apart, they don't hit each other. This is the case for ships
+                // that start out at the same position, for example.
+                Some(_) =>
+                    if relative_velocity.dot(&a_to_b) <= 0.0 {
+                        continue;
+                    },
 
-            if (body.position - planet.position).sqnorm() < squared_radius {
-                game_state.to_destroy.push(body_id);
+                None =>
+                    continue,
+            }
+
+            // Slowly drifting into a ship is fine for anything that isn't a
+            // ship itself. Otherwise collecting cargo would be impossible.
+            let min_speed = if hull_a && hull_b {
+                MIN_IMPACT_SPEED
+            }
+            else {
+                MIN_DEBRIS_SPEED
+            };
+
+            if relative_velocity.norm() < min_speed {
+                continue;
+            }
+
+            impacts.push((a.id, b.id));
+        }
+    }
+
+    for (id_a, id_b) in impacts {
+        let a = entities.bodies[&id_a];
+        let b = entities.bodies[&id_b];
+
+        // Treat the impact as perfectly inelastic. All of the kinetic energy
+        // of the relative motion turns into damage, and both bodies continue
+        // with their common velocity. This also makes sure an impact doesn't
+        // deal damage again during the next update.
+        let reduced_mass = a.mass * b.mass / (a.mass + b.mass);
+        let speed        = (a.velocity - b.velocity).norm();
+        let energy       = 0.5 * reduced_mass * speed * speed;
+        let damage       = energy * DAMAGE_PER_ENERGY;
+
+        let velocity = (a.velocity * a.mass + b.velocity * b.mass)
+            / (a.mass + b.mass);
+        entities.bodies.get_mut(&id_a).unwrap().velocity = velocity;
+        entities.bodies.get_mut(&id_b).unwrap().velocity = velocity;
+
+        let collision =
+            entities.hulls.contains_key(&id_a)
+            && entities.hulls.contains_key(&id_b);
+
+        let (source_a, source_b) = if collision {
+            (DamageSource::Collision(id_b), DamageSource::Collision(id_a))
+        }
+        else {
+            (DamageSource::Debris, DamageSource::Debris)
+        };
+
+        game_state.damage.push((id_a, damage, source_a));
+        game_state.damage.push((id_b, damage, source_b));
+
+        // Let both parties know what happened, in case they survive.
+        if collision {
+            for &(id, other_id) in &[(id_a, id_b), (id_b, id_a)] {
+                if entities.ships.contains_key(&id) {
+                    game_state.notifications.push((
+                        id,
+                        Event::Collided(other_id, damage),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// A body that might be involved in an impact
+struct ImpactCandidate {
+    id      : EntityId,
+    body    : Body,
+    previous: Vec2<f64>,
+    radius  : f64,
+}
+
+impl ImpactCandidate {
+    /// Center of the circle that bounds the body's path
+    fn center(&self) -> Vec2<f64> {
+        (self.previous + self.body.position) * 0.5
+    }
+
+    /// Radius of the circle that bounds the body's path
+    fn reach(&self) -> f64 {
+        (self.body.position - self.previous).norm() * 0.5 + self.radius
+    }
+}
+
+/// Returns where a body was before it was moved during the current update.
+/// Bodies that have been created after that haven't moved yet.
+fn previous_position(game_state: &GameState, id: EntityId, body: &Body)
+    -> Vec2<f64>
+{
+    match game_state.previous_positions.get(&id) {
+        Some(position) => *position,
+        None           => body.position,
+    }
+}
+
+pub fn apply_damage(game_state: &mut GameState) {
+    let mut destroyed_ships = Vec::new();
+
+    for (id, damage, source) in game_state.damage.drain(..) {
+        match game_state.entities.hulls.get_mut(&id) {
+            Some(hull) => {
+                if hull.is_destroyed() {
+                    // Already destroyed by earlier damage during this update.
+                    continue;
+                }
+
+                hull.integrity -= damage;
+
+                if hull.is_destroyed() {
+                    game_state.to_destroy.push(id);
+
+                    if game_state.entities.ships.contains_key(&id) {
+                        game_state.notifications.push((
+                            id,
+                            Event::ShipDestroyed(source),
+                        ));
+
+                        destroyed_ships.push((id, source));
+                    }
+                }
+            },
+            None => {
+                let is_debris = game_state.entities.wrecks.contains_key(&id)
+                    || game_state.entities.containers.contains_key(&id);
+                if is_debris && damage < DEBRIS_DAMAGE_THRESHOLD {
+                    continue;
+                }
+
+                // Anything else without a hull doesn't survive any damage.
+                game_state.to_destroy.push(id);
+            },
+        }
+    }
+
+    for (ship_id, source) in destroyed_ships {
+        if let DamageSource::PlanetImpact(_) = source {
+            // Nothing is left of a ship that crashed into a planet.
+            continue;
+        }
+
+        spawn_wreck(game_state, ship_id);
+    }
+}
+
+/// Leaves a wreck behind in place of a destroyed ship. The wreck keeps moving
+/// along the ship's trajectory and contains whatever survived.
+fn spawn_wreck(game_state: &mut GameState, ship_id: EntityId) {
+    let ship = match game_state.entities.bodies.get(&ship_id) {
+        Some(ship) => *ship,
+        None       => return,
+    };
+
+    let mut items = match game_state.entities.cargo_holds.get(&ship_id) {
+        Some(cargo) => cargo.items.clone(),
+        None        => Vec::new(),
+    };
+
+    let wreck = {
+        let cargo_mass = items.iter().fold(0.0, |mass, item| mass + item.mass);
+
+        items.push(CargoItem {
+            kind: CargoKind::Scrap,
+            mass: (ship.mass - cargo_mass) * SCRAP_FRACTION,
+        });
+
+        Wreck {
+            items     : items,
+            stripped_s: None,
+        }
+    };
+
+    // Whatever can't be salvaged is lost in the destruction, so the wreck
+    // weighs as much as its items.
+    game_state.entities.create_entity()
+        .with_body(Body {
+            position: ship.position,
+            velocity: ship.velocity,
+            force   : Vec2::new(0.0, 0.0),
+            mass    : wreck.mass(),
+        })
+        .with_signature(Signature {
+            size: ship.mass,
+            heat: 0.0,
+        })
+        .with_wreck(wreck);
+}
+
+/// Drones transfer salvage from their target wreck into the ship's cargo hold,
+/// a bit at a time. They return, once the wreck has been stripped, the cargo
+/// hold is full or the wreck has gone out of range.
+pub fn harvest_wrecks(game_state: &mut GameState, delta_t_s: f64) {
+    let entities = &mut game_state.entities;
+
+    for (&ship_id, drone_bay) in &mut entities.drone_bays {
+        let wreck_id = match drone_bay.target {
+            Some(wreck_id) => wreck_id,
+            None           => continue,
+        };
+
+        let in_range = match (
+            entities.bodies.get(&ship_id),
+            entities.bodies.get(&wreck_id),
+        ) {
+            (Some(ship), Some(wreck)) =>
+                (ship.position - wreck.position).norm() <= DRONE_RANGE,
+            _ =>
+                false,
+        };
+        let (wreck, cargo) = match (
+            entities.wrecks.get_mut(&wreck_id),
+            entities.cargo_holds.get_mut(&ship_id),
+        ) {
+            (Some(wreck), Some(cargo)) if in_range => (wreck, cargo),
+            _ => {
+                drone_bay.target = None;
+                continue;
+            },
+        };
+
+        let mut budget = drone_bay.drones as f64 * drone_bay.rate * delta_t_s;
+        budget = budget.min(cargo.free_capacity());
+
+        let mut salvaged = 0.0;
+        while budget > 0.0 && !wreck.items.is_empty() {
+            let kind = wreck.items[0].kind;
+            let mass = wreck.items[0].mass.min(budget);
+
+            wreck.items[0].mass -= mass;
+            if wreck.items[0].mass <= 0.0 {
+                wreck.items.remove(0);
+            }
+
+            // Drones bring back pieces of an item, so we add to an item of
+            // the same kind, if there already is one.
+            match cargo.items.iter_mut().find(|item| item.kind == kind) {
+                Some(item) => item.mass += mass,
+                None       => cargo.items.push(CargoItem {
+                    kind: kind,
+                    mass: mass,
+                }),
+            }
+
+            budget   -= mass;
+            salvaged += mass;
+        }
+
+        drone_bay.salvaged += salvaged;
+        entities.bodies.get_mut(&ship_id).unwrap().mass  += salvaged;
+        entities.bodies.get_mut(&wreck_id).unwrap().mass  = wreck.mass();
+
+        if wreck.items.is_empty() {
+            if wreck.stripped_s.is_none() {
+                wreck.stripped_s = Some(game_state.time_s);
+            }
+            drone_bay.target = None;
+        }
+        else if cargo.free_capacity() <= 0.0 {
+            drone_bay.target = None;
+        }
+    }
+}
+
+pub fn decay_wrecks(game_state: &mut GameState) {
+    for (&id, wreck) in &game_state.entities.wrecks {
+        if let Some(stripped_s) = wreck.stripped_s {
+            if game_state.time_s >= stripped_s + WRECK_DECAY_S {
+                game_state.to_destroy.push(id);
+            }
+        }
+    }
+}
+
+pub fn detonate_missiles(game_state: &mut GameState) {
+    let mut detonations = Vec::new();
+
+    for (&id, missile) in &game_state.entities.missiles {
+        let body = match game_state.entities.bodies.get(&id) {
+            Some(body) => body,
+            None       => continue,
+        };
+        let target = match game_state.entities.bodies.get(&missile.target) {
+            Some(target) => target,
+            None         => {
+                // The target is gone. The missile has nothing left to do.
+                game_state.to_destroy.push(id);
+                continue;
+            },
+        };
+
+        let to_target         = target.position - body.position;
+        let relative_velocity = body.velocity - target.velocity;
+
+        if to_target.norm() <= missile.detonation_range {
+            detonations.push((id, body.position, *missile));
+        }
+        else if
+            missile.fuel_s <= 0.0 && relative_velocity.dot(&to_target) < 0.0
+        {
+            // The missile has run out of fuel and is moving away from its
+            // target. It won't hit anymore, so we get rid of it.
+            game_state.to_destroy.push(id);
+        }
+    }
+
+    if detonations.is_empty() {
+        return;
+    }
+
+    let mut index = SpatialIndex::new(INDEX_CELL_SIZE);
+    for (&id, body) in &game_state.entities.bodies {
+        index.insert(id, body.position);
+    }
+
+    for (missile_id, position, missile) in detonations {
+        game_state.to_destroy.push(missile_id);
+
+        for body_id in index.neighbours(position, missile.blast_radius) {
+            if body_id == missile_id {
+                continue;
+            }
+
+            let body     = game_state.entities.bodies[&body_id];
+            let distance = (body.position - position).norm();
+            if distance <= missile.blast_radius {
+                // The blast is most dangerous at its center, and falls off
+                // linearly towards its edge.
+                let damage =
+                    missile.damage * (1.0 - distance / missile.blast_radius);
+
+                game_state.damage.push((
+                    body_id,
+                    damage,
+                    DamageSource::MissileBlast(missile.owner),
+                ));
             }
         }
     }
 }
+
+pub fn detect_entities(game_state: &mut GameState) {
+    let detections = {
+        let entities = &game_state.entities;
+
+        let mut thrust = HashMap::new();
+        for (_, maneuver) in &entities.maneuvers {
+            if game_state.time_s < maneuver.data.start_s {
+                continue;
+            }
+            // Maneuvers at zero thrust don't fire the engines.
+            if maneuver.data.thrust <= 0.0 {
+                continue;
+            }
+            if let Some(ship) = entities.ships.get(&maneuver.ship_id) {
+                *thrust.entry(maneuver.ship_id).or_insert(0.0) +=
+                    maneuver.data.thrust * ship.thrust;
+            }
+        }
+        for (&id, missile) in &entities.missiles {
+            if missile.fuel_s > 0.0 {
+                *thrust.entry(id).or_insert(0.0) += missile.thrust;
+            }
+        }
+
+        let mut detections = HashMap::new();
+
+        for (&observer_id, sensors) in &entities.sensors {
+            let observer = match entities.bodies.get(&observer_id) {
+                Some(observer) => observer,
+                None           => continue,
+            };
+
+            let mut detected = HashMap::new();
+
+            for (&target_id, signature) in &entities.signatures {
+                if target_id == observer_id {
+                    continue;
+                }
+
+                let target = match entities.bodies.get(&target_id) {
+                    Some(target) => target,
+                    None         => continue,
+                };
+
+                let target_thrust =
+                    thrust.get(&target_id).map_or(0.0, |&thrust| thrust);
+
+                let mut emission = signature.size + signature.heat;
+                emission += target_thrust * HEAT_PER_THRUST;
+                if let Some(target_sensors) = entities.sensors.get(&target_id) {
+                    if target_sensors.radar_on {
+                        emission += RADAR_EMISSION;
+                    }
+                }
+
+                // Emissions fall off with the square of the distance, so the
+                // range at which something can be seen grows with the square
+                // root of its emissions. Radar has to travel both ways, which
+                // makes it even harder to pick up small objects.
+                let mut range = sensors.range * emission.sqrt();
+                if sensors.radar_on {
+                    range = range.max(
+                        sensors.radar_range * signature.size.sqrt().sqrt()
+                    );
+                }
+
+                let to_target = target.position - observer.position;
+                let distance  = to_target.norm();
+
+                range = range * sensors.focus_factor(angle_of(to_target));
+
+                let strength = if distance > 0.0 {
+                    range / distance
+                }
+                else {
+                    f64::INFINITY
+                };
+
+                if strength < 1.0 {
+                    continue;
+                }
+
+                // A burning engine gives away what kind of object it is,
+                // even if it can't be made out otherwise.
+                let quality = if strength >= IDENTIFICATION_STRENGTH {
+                    DetectionQuality::Identified
+                }
+                else if
+                    strength >= CLASSIFICATION_STRENGTH
+                    || target_thrust > 0.0
+                {
+                    DetectionQuality::Classified
+                }
+                else {
+                    DetectionQuality::Unknown
+                };
+
+                detected.insert(target_id, Detection {
+                    quality : quality,
+                    strength: strength,
+                });
+            }
+
+            detections.insert(observer_id, detected);
+        }
+
+        detections
+    };
+
+    // Clients need to forget about objects that their ship no longer detects,
+    // or that it can no longer identify.
+    for (&observer_id, previous) in &game_state.detections {
+        let current = match detections.get(&observer_id) {
+            Some(current) => current,
+            None          => continue,
+        };
+
+        for (&target_id, detection) in previous {
+            let lost = match current.get(&target_id) {
+                Some(now) =>
+                    detection.quality == DetectionQuality::Identified
+                    && now.quality != DetectionQuality::Identified,
+                None =>
+                    true,
+            };
+
+            // Destroyed entities have already been removed for everyone.
+            if lost && game_state.entities.entities.contains(&target_id) {
+                game_state.notifications.push((
+                    observer_id,
+                    Event::RemoveEntity(target_id),
+                ));
+            }
+        }
+    }
+
+    game_state.detections = detections;
+}
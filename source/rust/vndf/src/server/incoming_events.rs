@@ -103,9 +103,11 @@ fn handle_event(
 
             handle_privileged_event(
                 now_s,
+                address,
                 event,
                 client,
                 game_state,
+                outgoing_events,
             );
         },
     }
@@ -120,18 +122,34 @@ fn handle_public_event(
     outgoing_events: &mut OutgoingEvents,
 ) {
     match event {
-        client::event::Public::Login => {
+        client::event::Public::Login(design, spawner) => {
             // TODO: Move parts of this code into Client, as Client::login.
             if clients.clients.contains_key(&address) {
                 debug!("Ignoring duplicate login: {}", address);
             }
             else {
-                let ship_id = game_state
-                    .handle_event(events::Enter)
-                    .unwrap(); // Enter event always validates
+                let result = game_state.handle_event(events::Enter {
+                    design : design.clone(),
+                    spawner: spawner.clone(),
+                });
+
+                let ship_id = match result {
+                    Ok(ship_id) => ship_id,
+                    Err(reason) => {
+                        warn!("Invalid login: {} ({})", address, reason);
+
+                        outgoing_events.push(
+                            server::Event::Rejected(reason),
+                            Recipients::One(address),
+                        );
+                        return;
+                    },
+                };
 
                 let client = Client {
                     ship_id      : ship_id,
+                    design       : design,
+                    spawner      : spawner,
                     last_active_s: now_s,
                 };
 
@@ -147,10 +165,12 @@ fn handle_public_event(
 }
 
 fn handle_privileged_event(
-    now_s     : f64,
-    event     : client::event::Privileged,
-    client    : &mut Client,
-    game_state: &mut GameState,
+    now_s          : f64,
+    address        : SocketAddr,
+    event          : client::event::Privileged,
+    client         : &mut Client,
+    game_state     : &mut GameState,
+    outgoing_events: &mut OutgoingEvents,
 ) {
     client.last_active_s = now_s;
 
@@ -161,6 +181,22 @@ fn handle_privileged_event(
             // updated.
             return;
         },
+        client::event::Privileged::Respawn => {
+            let result = game_state.handle_event(events::Respawn {
+                ship_id: client.ship_id,
+                design : client.design.clone(),
+                spawner: client.spawner.clone(),
+            });
+
+            result.map(|ship_id| {
+                client.ship_id = ship_id;
+
+                outgoing_events.push(
+                    server::Event::ShipId(ship_id),
+                    Recipients::One(address),
+                );
+            })
+        },
         client::event::Privileged::StartBroadcast(message) => {
             game_state.handle_event(events::StartBroadcast {
                 ship_id: client.ship_id,
@@ -178,6 +214,13 @@ fn handle_privileged_event(
                 data   : data,
             })
         },
+        client::event::Privileged::UpdateManeuver(maneuver_id, data) => {
+            game_state.handle_event(events::UpdateManeuver {
+                ship_id    : client.ship_id,
+                maneuver_id: maneuver_id,
+                data       : data,
+            })
+        },
         client::event::Privileged::CancelManeuver(maneuver_id) => {
             game_state.handle_event(events::CancelManeuver {
                 ship_id    : client.ship_id,
@@ -190,9 +233,92 @@ fn handle_privileged_event(
                 target_time_s: target_time_s,
             })
         },
+        client::event::Privileged::EjectCargo(index) => {
+            game_state
+                .handle_event(events::EjectCargo {
+                    ship_id: client.ship_id,
+                    index  : index,
+                })
+                .map(|_| ())
+        },
+        client::event::Privileged::CollectCargo(container_id) => {
+            game_state.handle_event(events::CollectCargo {
+                ship_id     : client.ship_id,
+                container_id: container_id,
+            })
+        },
+        client::event::Privileged::SalvageWreck(wreck_id) => {
+            game_state.handle_event(events::SalvageWreck {
+                ship_id : client.ship_id,
+                wreck_id: wreck_id,
+            })
+        },
+        client::event::Privileged::DispatchDrones(wreck_id) => {
+            game_state.handle_event(events::DispatchDrones {
+                ship_id : client.ship_id,
+                wreck_id: wreck_id,
+            })
+        },
+        client::event::Privileged::RecallDrones => {
+            game_state.handle_event(events::RecallDrones {
+                ship_id: client.ship_id,
+            })
+        },
+        client::event::Privileged::Dock(station_id) => {
+            game_state.handle_event(events::Dock {
+                ship_id   : client.ship_id,
+                station_id: station_id,
+            })
+        },
+        client::event::Privileged::Undock => {
+            game_state.handle_event(events::Undock {
+                ship_id: client.ship_id,
+            })
+        },
+        client::event::Privileged::Refuel => {
+            game_state.handle_event(events::Refuel {
+                ship_id: client.ship_id,
+            })
+        },
+        client::event::Privileged::Repair => {
+            game_state.handle_event(events::Repair {
+                ship_id: client.ship_id,
+            })
+        },
+        client::event::Privileged::LaunchMissile(target_id) => {
+            game_state
+                .handle_event(events::LaunchMissile {
+                    ship_id  : client.ship_id,
+                    target_id: target_id,
+                })
+                .map(|_| ())
+        },
+        client::event::Privileged::SetLauncherMode(mode) => {
+            game_state.handle_event(events::SetLauncherMode {
+                ship_id: client.ship_id,
+                mode   : mode,
+            })
+        },
+        client::event::Privileged::SetRadar(active) => {
+            game_state.handle_event(events::SetRadar {
+                ship_id: client.ship_id,
+                active : active,
+            })
+        },
+        client::event::Privileged::SetSensorFocus(focus) => {
+            game_state.handle_event(events::SetSensorFocus {
+                ship_id: client.ship_id,
+                focus  : focus,
+            })
+        },
     };
 
-    if let Err(()) = result {
-        warn!("Invalid event from client: {:?}", client);
+    if let Err(reason) = result {
+        warn!("Invalid event from client: {:?} ({})", client, reason);
+
+        outgoing_events.push(
+            server::Event::Rejected(reason),
+            Recipients::One(address),
+        );
     }
 }
@@ -1,3 +1,6 @@
+use std::f64::consts::PI;
+use std::fmt;
+
 use nalgebra::Vec2;
 
 use shared::color::Color;
@@ -6,6 +9,10 @@ use shared::color::Color;
 pub type EntityId = u64;
 
 
+/// Outside of the focus cone, sensor range is reduced by this factor
+const FOCUS_PENALTY: f64 = 0.5;
+
+
 #[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
 pub struct Body {
     pub position: Vec2<f64>,
@@ -20,6 +27,185 @@ pub struct Broadcast {
     pub message: String,
 }
 
+#[derive(Clone, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Cargo {
+    pub capacity: f64, // maximum mass the cargo hold can carry
+    pub items   : Vec<CargoItem>,
+}
+
+impl Cargo {
+    pub fn new(capacity: f64) -> Cargo {
+        Cargo {
+            capacity: capacity,
+            items   : Vec::new(),
+        }
+    }
+
+    pub fn mass(&self) -> f64 {
+        total_mass(&self.items)
+    }
+
+    pub fn free_capacity(&self) -> f64 {
+        self.capacity - self.mass()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct CargoItem {
+    pub kind: CargoKind,
+    pub mass: f64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, RustcDecodable, RustcEncodable)]
+pub enum CargoKind {
+    Fuel,
+    Ore,
+    Metal,
+    Scrap,
+    Supplies,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub enum DamageSource {
+    PlanetImpact(EntityId), // the planet that was hit
+    MissileBlast(EntityId), // the ship that launched the missile
+    Collision(EntityId),    // the other ship
+    Debris,
+}
+
+impl fmt::Display for DamageSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DamageSource::PlanetImpact(id) =>
+                write!(f, "impact on planet {}", id),
+            DamageSource::MissileBlast(id) =>
+                write!(f, "missile launched by {}", id),
+            DamageSource::Collision(id) =>
+                write!(f, "collision with ship {}", id),
+            DamageSource::Debris =>
+                write!(f, "high-speed debris"),
+        }
+    }
+}
+
+/// A free-floating container of cargo, usually left behind by a ship that
+/// ejected it.
+#[derive(Clone, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Container {
+    pub items: Vec<CargoItem>,
+}
+
+impl Container {
+    pub fn mass(&self) -> f64 {
+        total_mass(&self.items)
+    }
+}
+
+/// Attaches a ship to the station it has docked at
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Docking {
+    pub station_id: EntityId,
+}
+
+/// Houses drones that harvest wrecks for their owner.
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct DroneBay {
+    pub drones  : u32,
+    pub rate    : f64, // mass each drone transfers per second
+    pub target  : Option<EntityId>, // the wreck the drones are harvesting
+    pub salvaged: f64, // mass salvaged since the drones were dispatched
+}
+
+impl DroneBay {
+    pub fn new(drones: u32) -> DroneBay {
+        DroneBay {
+            drones  : drones,
+            rate    : 0.01,
+            target  : None,
+            salvaged: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct FtlDrive {
+    pub max_jump_s: f64, // longest jump the drive can perform
+    pub recharge_s: f64, // time the drive needs between jumps
+    pub ready_s   : f64, // game time at which the next jump is possible
+    pub fuel_s    : f64, // total jump time left, until the ship refuels
+    pub max_fuel_s: f64, // total jump time the fuel of a full ship lasts
+}
+
+impl FtlDrive {
+    pub fn new(max_jump_s: f64, max_fuel_s: f64) -> FtlDrive {
+        FtlDrive {
+            max_jump_s: max_jump_s,
+            recharge_s: 10.0,
+            ready_s   : 0.0,
+            fuel_s    : max_fuel_s,
+            max_fuel_s: max_fuel_s,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Hull {
+    pub integrity    : f64,
+    pub max_integrity: f64,
+}
+
+impl Hull {
+    pub fn new(max_integrity: f64) -> Hull {
+        Hull {
+            integrity    : max_integrity,
+            max_integrity: max_integrity,
+        }
+    }
+
+    pub fn is_destroyed(&self) -> bool {
+        self.integrity <= 0.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Launcher {
+    pub mode        : LauncherMode,
+    pub missiles    : u32, // missiles left in the magazine
+    pub missile_mass: f64,
+    pub reload_s    : f64, // time between two launches
+    pub ready_s     : f64, // game time at which the next launch is possible
+
+    // inbound missiles closer than this are intercepted in defensive mode
+    pub defense_range: f64,
+}
+
+impl Launcher {
+    pub fn new(missiles: u32) -> Launcher {
+        Launcher {
+            mode        : LauncherMode::Offensive,
+            missiles    : missiles,
+            missile_mass: 0.05,
+            reload_s    : 5.0,
+            ready_s     : 0.0,
+
+            defense_range: 1000.0,
+        }
+    }
+
+    pub fn mass(&self) -> f64 {
+        self.missiles as f64 * self.missile_mass
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, RustcDecodable, RustcEncodable)]
+pub enum LauncherMode {
+    /// Missiles are only launched on command.
+    Offensive,
+
+    /// Missiles are launched automatically, to intercept inbound missiles.
+    Defensive,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
 pub struct Maneuver {
     pub ship_id: EntityId,
@@ -28,10 +214,110 @@ pub struct Maneuver {
 
 #[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
 pub struct ManeuverData {
-    pub start_s   : f64,
-    pub duration_s: f64,
-    pub angle     : f64,
-    pub thrust    : f64, // 0.0 = 0%, 1.0 = 100%
+    pub start_s    : f64,
+    pub duration_s : f64,
+    pub angle      : f64,
+    pub thrust     : f64, // 0.0 = 0%, 1.0 = 100%
+    pub orientation: Orientation,
+}
+
+impl ManeuverData {
+    pub fn end_s(&self) -> f64 {
+        self.start_s + self.duration_s
+    }
+}
+
+/// Determines the direction of a maneuver's thrust. Except for `Fixed`, the
+/// direction is re-evaluated on every update, so it follows the ship as it
+/// moves. If the direction can't be determined (the ship doesn't move, or the
+/// reference entity doesn't exist or isn't detected anymore), the maneuver's
+/// angle is used instead.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, RustcDecodable, RustcEncodable)]
+pub enum Orientation {
+    /// Thrust in the direction of the maneuver's angle.
+    Fixed,
+
+    /// Thrust in the direction the ship is moving.
+    Prograde,
+
+    /// Thrust against the direction the ship is moving.
+    Retrograde,
+
+    /// Thrust towards the reference entity.
+    RadialIn(EntityId),
+
+    /// Thrust away from the reference entity.
+    RadialOut(EntityId),
+
+    /// Thrust at a right angle to the reference entity, counterclockwise
+    /// around it.
+    Normal(EntityId),
+
+    /// Thrust straight at the target entity.
+    Target(EntityId),
+
+    /// Thrust to match the velocity of the reference entity. The thrust stops
+    /// pointing anywhere useful once the velocities match, so the maneuver
+    /// should end around that time.
+    MatchVelocity(EntityId),
+}
+
+impl Orientation {
+    /// Returns the entity the orientation is relative to, if any.
+    pub fn reference(&self) -> Option<EntityId> {
+        match *self {
+            Orientation::Fixed             => None,
+            Orientation::Prograde          => None,
+            Orientation::Retrograde        => None,
+            Orientation::RadialIn(id)      => Some(id),
+            Orientation::RadialOut(id)     => Some(id),
+            Orientation::Normal(id)        => Some(id),
+            Orientation::Target(id)        => Some(id),
+            Orientation::MatchVelocity(id) => Some(id),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Missile {
+    pub owner : EntityId, // the ship that launched the missile
+    pub target: EntityId,
+    pub fuel_s: f64, // remaining burn time
+    pub thrust: f64,
+
+    pub detonation_range: f64, // distance to the target that triggers it
+    pub blast_radius    : f64,
+    pub damage          : f64, // damage at the center of the blast
+}
+
+impl Missile {
+    pub fn new(owner: EntityId, target: EntityId) -> Missile {
+        Missile {
+            owner : owner,
+            target: target,
+            fuel_s: 60.0,
+            thrust: 0.1,
+
+            detonation_range: 20.0,
+            blast_radius    : 50.0,
+            damage          : 60.0,
+        }
+    }
+
+    /// Creates a point-defense missile. Interceptors are only meant to take
+    /// out other missiles, so their blast is a lot weaker.
+    pub fn interceptor(owner: EntityId, target: EntityId) -> Missile {
+        Missile {
+            owner : owner,
+            target: target,
+            fuel_s: 30.0,
+            thrust: 0.1,
+
+            detonation_range: 25.0,
+            blast_radius    : 40.0,
+            damage          : 10.0,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
@@ -43,4 +329,150 @@ pub struct Planet {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
-pub struct Ship;
+pub struct Detection {
+    pub quality : DetectionQuality,
+    pub strength: f64, // 1.0 means the object is barely detectable
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, RustcDecodable, RustcEncodable)]
+pub enum DetectionQuality {
+    /// Something is there, but it's not known what it is.
+    Unknown,
+
+    /// It's known what kind of object it is, but nothing more.
+    Classified,
+
+    /// The object has been identified.
+    Identified,
+}
+
+/// An object that has been detected, but not identified. Everything about it
+/// is an estimate.
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Contact {
+    pub classification: Classification,
+    pub position      : Vec2<f64>,
+    pub velocity      : Vec2<f64>,
+    pub uncertainty   : f64, // maximum error of the estimated position
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, RustcDecodable, RustcEncodable)]
+pub enum Classification {
+    Unknown,
+    Ship,
+    Missile,
+    Container,
+    Wreck,
+    Station,
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Classification::Unknown   => write!(f, "unknown object"),
+            Classification::Ship      => write!(f, "ship"),
+            Classification::Missile   => write!(f, "missile"),
+            Classification::Container => write!(f, "container"),
+            Classification::Wreck     => write!(f, "wreck"),
+            Classification::Station   => write!(f, "station"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct SensorFocus {
+    pub angle: f64, // direction of the center of the cone, in radians
+    pub width: f64, // opening angle of the cone, in radians
+}
+
+impl SensorFocus {
+    pub fn contains(&self, direction: f64) -> bool {
+        let mut difference = (direction - self.angle) % (2.0 * PI);
+        if difference > PI {
+            difference -= 2.0 * PI;
+        }
+        if difference < -PI {
+            difference += 2.0 * PI;
+        }
+
+        difference.abs() <= self.width / 2.0
+    }
+
+    /// The narrower the cone, the further the sensors reach inside of it.
+    pub fn boost(&self) -> f64 {
+        (2.0 * PI / self.width).sqrt()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Sensors {
+    pub range      : f64, // passive range, for a signature strength of 1.0
+    pub radar_range: f64, // active range, for a size of 1.0
+    pub radar_on   : bool,
+    pub focus      : Option<SensorFocus>,
+}
+
+impl Sensors {
+    pub fn new(range: f64) -> Sensors {
+        Sensors {
+            range      : range,
+            radar_range: range * 2.0,
+            radar_on   : false,
+            focus      : None,
+        }
+    }
+
+    /// Returns the factor by which the sensor focus changes the range in the
+    /// given direction.
+    pub fn focus_factor(&self, direction: f64) -> f64 {
+        match self.focus {
+            Some(focus) =>
+                if focus.contains(direction) {
+                    focus.boost()
+                }
+                else {
+                    FOCUS_PENALTY
+                },
+            None =>
+                1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Ship {
+    pub thrust: f64, // force at 100% thrust
+    pub radius: f64, // size of the ship, for the purpose of collisions
+}
+
+/// Describes how easy an object is to detect.
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Signature {
+    pub size: f64, // determines optical visibility and radar reflections
+    pub heat: f64, // heat emitted when not thrusting
+}
+
+/// A station that ships can dock at, to be refuelled and repaired
+#[derive(Clone, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Station {
+    pub name: String,
+}
+
+
+/// What's left of a destroyed ship
+#[derive(Clone, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Wreck {
+    pub items     : Vec<CargoItem>, // what can still be salvaged
+    pub stripped_s: Option<f64>, // game time at which the last item was taken
+}
+
+impl Wreck {
+    pub fn mass(&self) -> f64 {
+        total_mass(&self.items)
+    }
+}
+
+
+fn total_mass(items: &[CargoItem]) -> f64 {
+    items.iter().fold(0.0, |mass, item| mass + item.mass)
+}
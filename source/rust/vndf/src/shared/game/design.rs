@@ -0,0 +1,208 @@
+use shared::game::data::{
+    Sensors,
+    Ship,
+    Signature,
+};
+
+
+/// Name of the design that ships use, if no other design is requested
+pub const DEFAULT_DESIGN: &'static str = "courier";
+
+/// Integrity of a ship's hull without any armor
+const BASE_INTEGRITY: f64 = 20.0;
+
+/// Collision radius of a ship with a mass of 1. Ships are assumed to have the
+/// same density, so the area they cover grows with their mass.
+const BASE_RADIUS: f64 = 10.0;
+
+
+/// A ship design is a list of modules. All of a ship's properties, like its
+/// mass or its thrust, are derived from its modules.
+#[derive(Clone, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct ShipDesign {
+    pub name   : String,
+    pub modules: Vec<Module>,
+}
+
+impl ShipDesign {
+    pub fn new(name: &str) -> ShipDesign {
+        ShipDesign {
+            name   : name.to_string(),
+            modules: Vec::new(),
+        }
+    }
+
+    pub fn with_module(
+        mut self,
+        kind    : ModuleKind,
+        mass    : f64,
+        capacity: f64,
+    ) -> ShipDesign {
+        self.modules.push(Module {
+            kind    : kind,
+            mass    : mass,
+            capacity: capacity,
+        });
+        self
+    }
+
+    /// Returns the designs that are available, unless the server has been
+    /// configured otherwise.
+    pub fn defaults() -> Vec<ShipDesign> {
+        vec![
+            ShipDesign::new(DEFAULT_DESIGN)
+                .with_module(ModuleKind::Armor     , 0.3, 80.0)
+                .with_module(ModuleKind::Cargo     , 0.2, 1.0)
+                .with_module(ModuleKind::Propulsion, 0.3, 1.0)
+                .with_module(ModuleKind::Weapons   , 0.1, 8.0)
+                .with_module(ModuleKind::FtlDrive  , 0.1, 3600.0)
+                .with_module(ModuleKind::Sensors   , 0.1, 10000.0),
+            ShipDesign::new("freighter")
+                .with_module(ModuleKind::Armor     , 0.3, 80.0)
+                .with_module(ModuleKind::Cargo     , 0.6, 4.0)
+                .with_module(ModuleKind::Propulsion, 0.5, 1.2)
+                .with_module(ModuleKind::FuelTank  , 0.2, 7200.0)
+                .with_module(ModuleKind::FtlDrive  , 0.2, 1800.0)
+                .with_module(ModuleKind::Sensors   , 0.1, 5000.0),
+            ShipDesign::new("gunship")
+                .with_module(ModuleKind::Armor     , 0.6, 200.0)
+                .with_module(ModuleKind::Cargo     , 0.1, 0.5)
+                .with_module(ModuleKind::Propulsion, 0.5, 1.5)
+                .with_module(ModuleKind::Weapons   , 0.1, 8.0)
+                .with_module(ModuleKind::Weapons   , 0.1, 8.0)
+                .with_module(ModuleKind::FtlDrive  , 0.2, 1800.0)
+                .with_module(ModuleKind::Sensors   , 0.1, 10000.0),
+            ShipDesign::new("prospector")
+                .with_module(ModuleKind::Armor     , 0.3, 80.0)
+                .with_module(ModuleKind::Cargo     , 0.4, 2.0)
+                .with_module(ModuleKind::Propulsion, 0.4, 1.0)
+                .with_module(ModuleKind::Refinery  , 0.4, 1.0)
+                .with_module(ModuleKind::DroneBay  , 0.2, 2.0)
+                .with_module(ModuleKind::FtlDrive  , 0.2, 1800.0)
+                .with_module(ModuleKind::Sensors   , 0.1, 10000.0),
+            ShipDesign::new("scout")
+                .with_module(ModuleKind::Armor     , 0.1, 20.0)
+                .with_module(ModuleKind::Cargo     , 0.1, 0.5)
+                .with_module(ModuleKind::Propulsion, 0.2, 1.0)
+                .with_module(ModuleKind::FtlDrive  , 0.1, 7200.0)
+                .with_module(ModuleKind::Sensors   , 0.2, 30000.0),
+        ]
+    }
+
+    /// Returns the design of the probes in probe swarms. Players can't choose
+    /// it, as it's not among the available designs.
+    pub fn probe() -> ShipDesign {
+        ShipDesign::new("probe")
+            .with_module(ModuleKind::Armor     , 0.1, 20.0)
+            .with_module(ModuleKind::Propulsion, 0.2, 1.0)
+            .with_module(ModuleKind::Sensors   , 0.1, 15000.0)
+    }
+
+    /// Mass of the empty ship, without cargo or missiles.
+    pub fn mass(&self) -> f64 {
+        self.modules.iter().fold(0.0, |mass, module| mass + module.mass)
+    }
+
+    pub fn thrust(&self) -> f64 {
+        self.capacity(ModuleKind::Propulsion)
+    }
+
+    pub fn cargo_capacity(&self) -> f64 {
+        self.capacity(ModuleKind::Cargo)
+    }
+
+    pub fn sensor_range(&self) -> f64 {
+        self.capacity(ModuleKind::Sensors)
+    }
+
+    pub fn radius(&self) -> f64 {
+        BASE_RADIUS * self.mass().sqrt()
+    }
+
+    pub fn integrity(&self) -> f64 {
+        BASE_INTEGRITY + self.capacity(ModuleKind::Armor)
+    }
+
+    pub fn missiles(&self) -> u32 {
+        self.capacity(ModuleKind::Weapons) as u32
+    }
+
+    pub fn drones(&self) -> u32 {
+        self.capacity(ModuleKind::DroneBay) as u32
+    }
+
+    pub fn max_jump_s(&self) -> f64 {
+        self.capacity(ModuleKind::FtlDrive)
+    }
+
+    /// An FTL drive carries enough fuel for one jump of maximum length. Fuel
+    /// tanks add to that.
+    pub fn ftl_fuel_s(&self) -> f64 {
+        self.max_jump_s() + self.capacity(ModuleKind::FuelTank)
+    }
+
+    pub fn has_module(&self, kind: ModuleKind) -> bool {
+        self.modules.iter().any(|module| module.kind == kind)
+    }
+
+    pub fn ship(&self) -> Ship {
+        Ship {
+            thrust: self.thrust(),
+            radius: self.radius(),
+        }
+    }
+
+    pub fn sensors(&self) -> Sensors {
+        Sensors::new(self.sensor_range())
+    }
+
+    /// Bigger ships are easier to see. Refineries run hot, even if the ship
+    /// isn't thrusting.
+    pub fn signature(&self) -> Signature {
+        Signature {
+            size: self.mass(),
+            heat: 0.1 + self.capacity(ModuleKind::Refinery),
+        }
+    }
+
+    fn capacity(&self, kind: ModuleKind) -> f64 {
+        self.modules
+            .iter()
+            .filter(|module| module.kind == kind)
+            .fold(0.0, |capacity, module| capacity + module.capacity)
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable, RustcEncodable)]
+pub struct Module {
+    pub kind: ModuleKind,
+    pub mass: f64,
+
+    /// What the capacity means depends on the kind of module:
+    /// - Armor: hull integrity
+    /// - Cargo: mass that can be stored
+    /// - Propulsion: thrust
+    /// - FuelTank: fuel that can be stored, in seconds of FTL jumps
+    /// - Weapons: missiles in the magazine
+    /// - Refinery: ore that can be processed per second
+    /// - DroneBay: number of harvesting drones
+    /// - Sensors: sensor range
+    /// - FtlDrive: longest FTL jump in seconds
+    pub capacity: f64,
+}
+
+
+// TODO: Refineries don't do much for now, as there's no refining yet.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, RustcDecodable, RustcEncodable)]
+pub enum ModuleKind {
+    Armor,
+    Cargo,
+    Propulsion,
+    FuelTank,
+    Weapons,
+    Refinery,
+    DroneBay,
+    Sensors,
+    FtlDrive,
+}
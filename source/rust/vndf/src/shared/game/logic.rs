@@ -1,4 +1,5 @@
 use nalgebra::{
+    Dot,
     Norm,
     Vec2,
 };
@@ -9,8 +10,11 @@ use shared::game::data::{
 };
 
 
+pub const GRAVITATIONAL_CONSTANT: f64 = 6.674e-11; // unit: N * m^2 / kg^2
+
+
 pub fn apply_gravity(planet: &Planet, body: &mut Body) {
-    let g = 6.674e-11; // unit: N * m^2 / kg^2
+    let g = GRAVITATIONAL_CONSTANT;
 
     let body_to_planet = body.position - planet.position;
     let distance       = body_to_planet.norm();
@@ -22,6 +26,52 @@ pub fn apply_gravity(planet: &Planet, body: &mut Body) {
     body.force = body.force + force;
 }
 
+pub fn is_inside(body: &Body, planet: &Planet) -> bool {
+    let squared_radius = planet.radius * planet.radius;
+    (body.position - planet.position).sqnorm() < squared_radius
+}
+
+/// Moves a point along a line segment, from `start` to `end`. Returns the
+/// fraction of the segment after which the point first touches the circle, or
+/// `None`, if it misses. Points that start inside the circle touch it at 0.0.
+///
+/// Checking only the end of each step would miss small objects that a fast
+/// body passes within a single step.
+pub fn sweep_circle(
+    start : Vec2<f64>,
+    end   : Vec2<f64>,
+    center: Vec2<f64>,
+    radius: f64,
+) -> Option<f64> {
+    let path   = end - start;
+    let offset = start - center;
+
+    let a = path.dot(&path);
+    let b = 2.0 * offset.dot(&path);
+    let c = offset.dot(&offset) - radius * radius;
+
+    if c <= 0.0 {
+        return Some(0.0);
+    }
+    if a == 0.0 {
+        // The point doesn't move.
+        return None;
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let t = (-b - discriminant.sqrt()) / (2.0 * a);
+    if t >= 0.0 && t <= 1.0 {
+        Some(t)
+    }
+    else {
+        None
+    }
+}
+
 pub fn integrate(body: &mut Body, delta_t_s: f64) {
     body.velocity = body.velocity + body.force / body.mass * delta_t_s;
     body.position = body.position + body.velocity          * delta_t_s;
@@ -1,2 +1,4 @@
 pub mod data;
+pub mod design;
 pub mod logic;
+pub mod navigation;
@@ -0,0 +1,555 @@
+use std::f64::consts::PI;
+
+use nalgebra::{
+    Dot,
+    Norm,
+    Vec2,
+};
+
+use shared::game::data::{
+    Body,
+    EntityId,
+    ManeuverData,
+    Orientation,
+    Planet,
+};
+use shared::game::logic::{
+    GRAVITATIONAL_CONSTANT,
+    apply_gravity,
+    integrate,
+    sweep_circle,
+};
+use shared::util::angle_of;
+
+
+/// Time step of trajectory predictions
+pub const PREDICTION_STEP_S: f64 = 0.5;
+
+/// Maximum number of steps a single prediction may take. Predictions run on
+/// the client, so they need to be fast.
+pub const MAX_PREDICTION_STEPS: usize = 10000;
+
+/// A plan is good enough, if it gets the ship this close to its target
+pub const ARRIVAL_TOLERANCE: f64 = 1.0;
+
+/// Maximum number of refinements of a plan, before the solver gives up
+const MAX_ITERATIONS: usize = 20;
+
+/// Hohmann transfers are calculated for circular orbits. Orbits that are more
+/// eccentric than this are too far off to plan a transfer from.
+pub const MAX_HOHMANN_ECCENTRICITY: f64 = 0.1;
+
+/// Change of velocity that is used to estimate how sensitive the arrival
+/// position is to the velocity at departure
+const VELOCITY_DELTA: f64 = 0.001;
+
+
+/// An instantaneous change of velocity at a given time. Ships can't actually
+/// change their velocity instantaneously, but as long as a burn is short
+/// compared to the rest of the plan, this is a good approximation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Burn {
+    pub time_s : f64,
+    pub delta_v: Vec2<f64>,
+}
+
+impl Burn {
+    /// Returns a maneuver that performs the burn at full thrust. The maneuver
+    /// is centered on the time of the burn, which makes up for most of the
+    /// error of treating the burn as instantaneous. Returns `None`, if the
+    /// burn doesn't change the velocity.
+    pub fn to_maneuver(&self, mass: f64, max_thrust: f64)
+        -> Option<ManeuverData>
+    {
+        let delta_v = self.delta_v.norm();
+        if delta_v == 0.0 || max_thrust <= 0.0 {
+            return None;
+        }
+
+        let duration_s = delta_v * mass / max_thrust;
+
+        Some(ManeuverData {
+            start_s    : self.time_s - duration_s / 2.0,
+            duration_s : duration_s,
+            angle      : angle_of(self.delta_v),
+            thrust     : 1.0,
+            orientation: Orientation::Fixed,
+        })
+    }
+}
+
+
+/// Something the ship can approach
+#[derive(Clone, Copy, Debug)]
+pub enum Target {
+    /// A body that moves under the influence of gravity
+    Body(Body),
+
+    /// A fixed position, like the center of a planet
+    Fixed(Vec2<f64>),
+}
+
+impl Target {
+    pub fn position(&self) -> Vec2<f64> {
+        match *self {
+            Target::Body(ref body)  => body.position,
+            Target::Fixed(position) => position,
+        }
+    }
+}
+
+
+/// Where the ship gets closest to a target
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Approach {
+    pub time_s  : f64,
+    pub distance: f64,
+    pub position: Vec2<f64>, // position of the ship at that time
+}
+
+
+/// The orbit of a body around a celestial, based on the body's current position
+/// and velocity. Only the celestial's gravity is taken into account.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Orbit {
+    pub eccentricity: f64,
+    pub periapsis   : f64,         // distance from the center of the celestial
+    pub apoapsis    : Option<f64>, // `None`, if the body escapes
+    pub period_s    : Option<f64>, // `None`, if the body escapes
+
+    /// The next apsis the body passes, and the time until then. `None`, if the
+    /// body escapes and has already passed its periapsis.
+    pub next_apsis: Option<(Apsis, f64)>,
+
+    /// Whether the body is going to hit the celestial
+    pub intersects_surface: bool,
+}
+
+impl Orbit {
+    /// Returns `None`, if the body is at the center of the celestial, or the
+    /// celestial doesn't have any mass.
+    pub fn of(body: &Body, center: &Planet) -> Option<Orbit> {
+        let mu = GRAVITATIONAL_CONSTANT * center.mass;
+
+        let r = body.position - center.position;
+        let v = body.velocity;
+
+        let distance = r.norm();
+        if distance == 0.0 || mu <= 0.0 {
+            return None;
+        }
+
+        let angular_momentum = r.x * v.y - r.y * v.x;
+        let energy           = v.sqnorm() / 2.0 - mu / distance;
+        let approaching      = r.dot(&v) < 0.0;
+
+        let eccentricity_vector =
+            (r * (v.sqnorm() - mu / distance) - v * r.dot(&v)) / mu;
+        let eccentricity = eccentricity_vector.norm();
+
+        let periapsis = angular_momentum * angular_momentum
+            / (mu * (1.0 + eccentricity));
+
+        // True anomaly, measured in the direction of motion
+        let e = eccentricity_vector;
+        let true_anomaly = (
+            angular_momentum.signum() * (e.x * r.y - e.y * r.x)
+        ).atan2(e.dot(&r));
+
+        let (apoapsis, period_s, next_apsis) = if eccentricity < 1.0 {
+            let semi_major_axis = -mu / (2.0 * energy);
+            let mean_motion     =
+                (mu / (semi_major_axis * semi_major_axis * semi_major_axis))
+                    .sqrt();
+
+            let eccentric_anomaly = 2.0 * (
+                (1.0 - eccentricity).sqrt() * (true_anomaly / 2.0).sin()
+            ).atan2(
+                (1.0 + eccentricity).sqrt() * (true_anomaly / 2.0).cos()
+            );
+            let mean_anomaly = normalize_angle(
+                eccentric_anomaly - eccentricity * eccentric_anomaly.sin()
+            );
+
+            let next_apsis = if mean_anomaly < PI {
+                (Apsis::Apoapsis, (PI - mean_anomaly) / mean_motion)
+            }
+            else {
+                (Apsis::Periapsis, (2.0 * PI - mean_anomaly) / mean_motion)
+            };
+
+            (
+                Some(semi_major_axis * (1.0 + eccentricity)),
+                Some(2.0 * PI / mean_motion),
+                Some(next_apsis),
+            )
+        }
+        else if eccentricity > 1.0 && approaching {
+            let semi_major_axis = -mu / (2.0 * energy); // negative
+            let mean_motion     =
+                (mu / -(semi_major_axis * semi_major_axis * semi_major_axis))
+                    .sqrt();
+
+            let hyperbolic_anomaly = 2.0 * (
+                ((eccentricity - 1.0) / (eccentricity + 1.0)).sqrt()
+                    * (true_anomaly / 2.0).tan()
+            ).atanh();
+            let mean_anomaly =
+                eccentricity * hyperbolic_anomaly.sinh() - hyperbolic_anomaly;
+
+            (None, None, Some((Apsis::Periapsis, -mean_anomaly / mean_motion)))
+        }
+        else {
+            (None, None, None)
+        };
+
+        // Bodies on escape trajectories only pass their periapsis, if they're
+        // still approaching it.
+        let intersects_surface = periapsis < center.radius
+            && (eccentricity < 1.0 || approaching);
+
+        Some(Orbit {
+            eccentricity: eccentricity,
+            periapsis   : periapsis,
+            apoapsis    : apoapsis,
+            period_s    : period_s,
+            next_apsis  : next_apsis,
+
+            intersects_surface: intersects_surface,
+        })
+    }
+}
+
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Apsis {
+    Periapsis,
+    Apoapsis,
+}
+
+
+/// Returns the planet whose gravity pulls the hardest at the given position.
+/// That's the planet that bodies at that position orbit.
+pub fn dominant_planet<'a, I>(position: Vec2<f64>, planets: I)
+    -> Option<(EntityId, &'a Planet)>
+    where I: IntoIterator<Item=(&'a EntityId, &'a Planet)>
+{
+    let mut dominant = None;
+    let mut max_pull = 0.0;
+
+    for (&id, planet) in planets {
+        let pull = planet.mass / (planet.position - position).sqnorm();
+
+        if pull > max_pull {
+            dominant = Some((id, planet));
+            max_pull = pull;
+        }
+    }
+
+    dominant
+}
+
+
+/// Predicts the state of a coasting body after the given time, using the same
+/// physics as the server. Returns `None`, if the body hits a planet on the
+/// way.
+pub fn predict(body: &Body, planets: &[Planet], duration_s: f64)
+    -> Option<Body>
+{
+    let mut body      = *body;
+    let mut elapsed_s = 0.0;
+
+    while elapsed_s < duration_s {
+        let delta_t_s = PREDICTION_STEP_S.min(duration_s - elapsed_s);
+        let previous  = body.position;
+
+        step(&mut body, planets, delta_t_s);
+        if first_hit(previous, body.position, planets).is_some() {
+            return None;
+        }
+
+        elapsed_s += delta_t_s;
+    }
+
+    Some(body)
+}
+
+/// Predicts when the ship will get closest to the target, within the given
+/// time span. The ship's path ends early, if it hits a planet. Returns `None`,
+/// if there is nothing to predict, or the prediction would take more than
+/// `MAX_PREDICTION_STEPS`.
+pub fn closest_approach(
+    ship      : &Body,
+    target    : Target,
+    planets   : &[Planet],
+    now_s     : f64,
+    duration_s: f64,
+    step_s    : f64,
+) -> Option<Approach> {
+    // Written this way, so NaNs are rejected too
+    let valid = duration_s > 0.0
+        && step_s > 0.0
+        && duration_s / step_s <= MAX_PREDICTION_STEPS as f64;
+    if !valid {
+        return None;
+    }
+
+    let mut ship   = *ship;
+    let mut target = target;
+    let mut offset = target.position() - ship.position;
+
+    let mut closest = Approach {
+        time_s  : now_s,
+        distance: offset.norm(),
+        position: ship.position,
+    };
+
+    let mut elapsed_s = 0.0;
+    while elapsed_s < duration_s {
+        let delta_t_s = step_s.min(duration_s - elapsed_s);
+
+        let previous_position = ship.position;
+        let previous_offset   = offset;
+
+        step(&mut ship, planets, delta_t_s);
+        if let Target::Body(ref mut body) = target {
+            step(body, planets, delta_t_s);
+        }
+        offset = target.position() - ship.position;
+
+        // If the ship hits a planet, its path ends within this step.
+        let hit   = first_hit(previous_position, ship.position, planets);
+        let end_t = hit.unwrap_or(1.0);
+
+        // Within a step, the bodies are assumed to move in straight lines
+        // relative to each other. This finds approaches between steps.
+        let change = offset - previous_offset;
+        let t = if change.sqnorm() > 0.0 {
+            (-previous_offset.dot(&change) / change.sqnorm())
+                .max(0.0)
+                .min(end_t)
+        }
+        else {
+            0.0
+        };
+
+        let distance = (previous_offset + change * t).norm();
+        if distance < closest.distance {
+            closest = Approach {
+                time_s  : now_s + elapsed_s + delta_t_s * t,
+                distance: distance,
+                position: previous_position
+                    + (ship.position - previous_position) * t,
+            };
+        }
+
+        elapsed_s += delta_t_s;
+
+        if hit.is_some() {
+            break;
+        }
+    }
+
+    Some(closest)
+}
+
+/// Plans a rendezvous with a target: A burn at departure that puts the ship on
+/// a course to meet the target at arrival, and a burn at arrival that matches
+/// the target's velocity. Both bodies are assumed to coast otherwise.
+///
+/// The course is found by refining the straight-line course, until it
+/// arrives at the target in spite of gravity (Newton's method, with the
+/// derivatives estimated from predictions). Returns `None`, if no course
+/// could be found, all courses hit a planet, or the arrival is too far in the
+/// future to predict.
+pub fn plan_rendezvous(
+    ship       : &Body,
+    target     : &Body,
+    planets    : &[Planet],
+    now_s      : f64,
+    departure_s: f64,
+    arrival_s  : f64,
+) -> Option<Vec<Burn>> {
+    let max_duration_s = MAX_PREDICTION_STEPS as f64 * PREDICTION_STEP_S;
+
+    // Written this way, so NaNs are rejected too
+    let valid = now_s <= departure_s
+        && departure_s < arrival_s
+        && arrival_s - now_s <= max_duration_s;
+    if !valid {
+        return None;
+    }
+
+    let transfer_s = arrival_s - departure_s;
+
+    let ship = match predict(ship, planets, departure_s - now_s) {
+        Some(ship) => ship,
+        None       => return None,
+    };
+    let target = match predict(target, planets, arrival_s - now_s) {
+        Some(target) => target,
+        None         => return None,
+    };
+
+    let arrive = |delta_v: Vec2<f64>| {
+        let mut ship = ship;
+        ship.velocity = ship.velocity + delta_v;
+        predict(&ship, planets, transfer_s)
+    };
+
+    let mut delta_v =
+        (target.position - ship.position) / transfer_s - ship.velocity;
+
+    for _ in 0 .. MAX_ITERATIONS {
+        let arrival = match arrive(delta_v) {
+            Some(arrival) => arrival,
+            None          => return None,
+        };
+
+        let miss = arrival.position - target.position;
+        if miss.norm() <= ARRIVAL_TOLERANCE {
+            return Some(vec![
+                Burn {
+                    time_s : departure_s,
+                    delta_v: delta_v,
+                },
+                Burn {
+                    time_s : arrival_s,
+                    delta_v: target.velocity - arrival.velocity,
+                },
+            ]);
+        }
+
+        // How the miss changes with each component of the velocity change
+        let dx = match arrive(delta_v + Vec2::new(VELOCITY_DELTA, 0.0)) {
+            Some(arrival) => (arrival.position - target.position - miss)
+                / VELOCITY_DELTA,
+            None          => return None,
+        };
+        let dy = match arrive(delta_v + Vec2::new(0.0, VELOCITY_DELTA)) {
+            Some(arrival) => (arrival.position - target.position - miss)
+                / VELOCITY_DELTA,
+            None          => return None,
+        };
+
+        let determinant = dx.x * dy.y - dy.x * dx.y;
+        if determinant == 0.0 {
+            return None;
+        }
+
+        delta_v = delta_v + Vec2::new(
+            (dy.x * miss.y - dy.y * miss.x) / determinant,
+            (dx.y * miss.x - dx.x * miss.y) / determinant,
+        );
+    }
+
+    None
+}
+
+/// Plans a Hohmann transfer from the ship's orbit around a celestial to a
+/// circular orbit of the given radius: A prograde burn that raises (or lowers)
+/// the opposite side of the orbit to the target radius, and a second burn
+/// there that circularizes the orbit.
+///
+/// If a target angle is given, the ship departs once it is opposite that
+/// angle, so it arrives there. This is used to meet a moon. Otherwise, it
+/// departs as early as possible. Returns `None`, if the ship isn't in a
+/// roughly circular orbit around the celestial (see
+/// `MAX_HOHMANN_ECCENTRICITY`), or the target orbit is below its surface.
+pub fn plan_hohmann(
+    ship                : &Body,
+    center              : &Planet,
+    target_radius       : f64,
+    target_angle        : Option<f64>,
+    now_s               : f64,
+    earliest_departure_s: f64,
+) -> Option<Vec<Burn>> {
+    let mu = GRAVITATIONAL_CONSTANT * center.mass;
+
+    let offset = ship.position - center.position;
+    let r1     = offset.norm();
+    let r2     = target_radius;
+
+    // The sign of the angular momentum tells us which way the ship orbits.
+    let angular_momentum =
+        offset.x * ship.velocity.y - offset.y * ship.velocity.x;
+    if angular_momentum == 0.0 || !(r2 > center.radius) || !r2.is_finite() {
+        return None;
+    }
+
+    let is_circular = Orbit::of(ship, center).map_or(false, |orbit|
+        orbit.eccentricity <= MAX_HOHMANN_ECCENTRICITY
+    );
+    if !is_circular {
+        return None;
+    }
+    let direction = angular_momentum.signum();
+
+    let angular_speed = direction * (mu / (r1 * r1 * r1)).sqrt();
+    let angle_at      = |time_s: f64|
+        angle_of(offset) + angular_speed * (time_s - now_s);
+
+    let mut departure_s = earliest_departure_s.max(now_s);
+    if let Some(target_angle) = target_angle {
+        let remaining = normalize_angle(
+            (target_angle + PI - angle_at(departure_s)) * direction
+        );
+        departure_s += remaining / angular_speed.abs();
+    }
+
+    let transfer_s = PI * ((r1 + r2).powi(3) / (8.0 * mu)).sqrt();
+    let arrival_s  = departure_s + transfer_s;
+
+    let prograde = |angle: f64|
+        Vec2::new(-angle.sin(), angle.cos()) * direction;
+
+    let delta_v_1 =
+        (mu / r1).sqrt() * ((2.0 * r2 / (r1 + r2)).sqrt() - 1.0);
+    let delta_v_2 =
+        (mu / r2).sqrt() * (1.0 - (2.0 * r1 / (r1 + r2)).sqrt());
+
+    let departure_angle = angle_at(departure_s);
+
+    Some(vec![
+        Burn {
+            time_s : departure_s,
+            delta_v: prograde(departure_angle) * delta_v_1,
+        },
+        Burn {
+            time_s : arrival_s,
+            delta_v: prograde(departure_angle + PI) * delta_v_2,
+        },
+    ])
+}
+
+
+/// Moves a coasting body ahead by one time step
+/// Returns the fraction of the path from `start` to `end` after which it first
+/// touches a planet, or `None`, if it doesn't touch any. Checking only the end
+/// of a step would miss small planets that are passed within a single step.
+fn first_hit(start: Vec2<f64>, end: Vec2<f64>, planets: &[Planet])
+    -> Option<f64>
+{
+    let mut first: Option<f64> = None;
+    for planet in planets {
+        let hit = sweep_circle(start, end, planet.position, planet.radius);
+        if let Some(t) = hit {
+            first = Some(first.map_or(t, |first| first.min(t)));
+        }
+    }
+
+    first
+}
+
+fn step(body: &mut Body, planets: &[Planet], delta_t_s: f64) {
+    for planet in planets {
+        apply_gravity(planet, body);
+    }
+    integrate(body, delta_t_s);
+}
+
+/// Normalizes an angle to the range [0, 2 * PI)
+fn normalize_angle(angle: f64) -> f64 {
+    let full_circle = 2.0 * PI;
+    ((angle % full_circle) + full_circle) % full_circle
+}
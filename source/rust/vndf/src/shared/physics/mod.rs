@@ -1,3 +1,5 @@
 pub mod collision;
+pub mod spatial_index;
 
 pub use shared::physics::collision::{SphereCollider,Sphere};
+pub use shared::physics::spatial_index::SpatialIndex;
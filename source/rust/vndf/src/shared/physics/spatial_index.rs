@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use nalgebra::{
+    Norm,
+    Vec2,
+};
+
+use shared::game::data::EntityId;
+
+
+/// Cell coordinates are clamped to this, so they always fit into an `i64`.
+/// Entities that are even further out share the cells at the edge.
+const MAX_CELL: f64 = 1e15;
+
+/// Sorts entities into the cells of a uniform grid, so the neighbours of a
+/// position can be found without looking at every single entity. The index is
+/// meant to be built from scratch whenever it's needed, for example once per
+/// update.
+///
+/// Queries are fastest if the cell size is about the same as the radius that
+/// is typically queried.
+#[derive(Debug)]
+pub struct SpatialIndex {
+    cell_size: f64,
+    cells    : HashMap<(i64, i64), Vec<(EntityId, Vec2<f64>)>>,
+}
+
+impl SpatialIndex {
+    /// Creates an empty index. `cell_size` must be positive.
+    pub fn new(cell_size: f64) -> SpatialIndex {
+        assert!(cell_size > 0.0, "Invalid cell size: {}", cell_size);
+
+        SpatialIndex {
+            cell_size: cell_size,
+            cells    : HashMap::new(),
+        }
+    }
+
+    /// Inserts an entity. Entities at non-finite positions can't be sorted
+    /// into a cell, and are left out.
+    pub fn insert(&mut self, id: EntityId, position: Vec2<f64>) {
+        if !position.x.is_finite() || !position.y.is_finite() {
+            return;
+        }
+
+        let cell = self.cell_of(position.x, position.y);
+
+        self.cells
+            .entry(cell)
+            .or_insert_with(|| Vec::new())
+            .push((id, position));
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// Returns all entities within `radius` of `position`, including any
+    /// entity at the position itself.
+    pub fn neighbours(&self, position: Vec2<f64>, radius: f64)
+        -> Vec<EntityId>
+    {
+        let mut neighbours = Vec::new();
+
+        if !position.x.is_finite() || !position.y.is_finite() {
+            return neighbours;
+        }
+        // Written this way, so NaNs are rejected too
+        if !(radius >= 0.0) {
+            return neighbours;
+        }
+
+        // If the query covers more cells than are occupied, it's cheaper to
+        // look at the occupied cells directly. This also keeps huge radii from
+        // iterating over an unbounded number of cells.
+        let cells_per_side = (2.0 * radius / self.cell_size).ceil() + 1.0;
+        if cells_per_side * cells_per_side > self.cells.len() as f64 {
+            for cell in self.cells.values() {
+                for &(id, other) in cell {
+                    if (other - position).norm() <= radius {
+                        neighbours.push(id);
+                    }
+                }
+            }
+
+            return neighbours;
+        }
+
+        let (min_x, min_y) =
+            self.cell_of(position.x - radius, position.y - radius);
+        let (max_x, max_y) =
+            self.cell_of(position.x + radius, position.y + radius);
+
+        for x in min_x .. max_x + 1 {
+            for y in min_y .. max_y + 1 {
+                let cell = match self.cells.get(&(x, y)) {
+                    Some(cell) => cell,
+                    None       => continue,
+                };
+
+                for &(id, other) in cell {
+                    if (other - position).norm() <= radius {
+                        neighbours.push(id);
+                    }
+                }
+            }
+        }
+
+        neighbours
+    }
+
+    fn cell_of(&self, x: f64, y: f64) -> (i64, i64) {
+        let cell = |coordinate: f64| {
+            (coordinate / self.cell_size).floor().max(-MAX_CELL).min(MAX_CELL)
+                as i64
+        };
+
+        (cell(x), cell(y))
+    }
+}
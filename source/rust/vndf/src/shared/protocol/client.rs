@@ -1,6 +1,8 @@
 use shared::game::data::{
 	EntityId,
+	LauncherMode,
 	ManeuverData,
+	SensorFocus,
 };
 
 
@@ -18,13 +20,28 @@ impl Event {
 		use self::event::Privileged::*;
 
 		match *self {
-			Event::Public(Login)                   => true,
+			Event::Public(Login(_, _))             => true,
 			Event::Privileged(Heartbeat)           => false,
+			Event::Privileged(Respawn)             => true,
 			Event::Privileged(StartBroadcast(_))   => true,
 			Event::Privileged(StopBroadcast)       => true,
 			Event::Privileged(ScheduleManeuver(_)) => true,
+			Event::Privileged(UpdateManeuver(..))  => true,
 			Event::Privileged(CancelManeuver(_))   => true,
 			Event::Privileged(FtlJump(_))          => true,
+			Event::Privileged(EjectCargo(_))       => true,
+			Event::Privileged(CollectCargo(_))     => true,
+			Event::Privileged(SalvageWreck(_))     => true,
+			Event::Privileged(DispatchDrones(_))   => true,
+			Event::Privileged(RecallDrones)        => true,
+			Event::Privileged(Dock(_))             => true,
+			Event::Privileged(Undock)              => true,
+			Event::Privileged(Refuel)              => true,
+			Event::Privileged(Repair)              => true,
+			Event::Privileged(LaunchMissile(_))    => true,
+			Event::Privileged(SetLauncherMode(_))  => true,
+			Event::Privileged(SetRadar(_))         => true,
+			Event::Privileged(SetSensorFocus(_))   => true,
 		}
 	}
 }
@@ -33,32 +50,59 @@ impl Event {
 pub mod event {
 	use shared::game::data::{
 		EntityId,
+		LauncherMode,
 		ManeuverData,
+		SensorFocus,
 	};
 
 
 	#[derive(Debug, PartialEq, RustcDecodable, RustcEncodable)]
 	pub enum Public {
-		Login,
+		Login(String, Option<String>), // ship design, spawner
 	}
 
 	#[derive(Debug, PartialEq, RustcDecodable, RustcEncodable)]
 	pub enum Privileged {
 		Heartbeat,
 
+		Respawn,
+
 		StartBroadcast(String),
 		StopBroadcast,
 
 		ScheduleManeuver(ManeuverData),
+		UpdateManeuver(EntityId, ManeuverData),
 		CancelManeuver(EntityId),
 
 		FtlJump(f64),
+
+		EjectCargo(usize),
+		CollectCargo(EntityId),
+		SalvageWreck(EntityId),
+
+		DispatchDrones(EntityId),
+		RecallDrones,
+
+		Dock(EntityId),
+		Undock,
+		Refuel,
+		Repair,
+
+		LaunchMissile(EntityId),
+		SetLauncherMode(LauncherMode),
+
+		SetRadar(bool),
+		SetSensorFocus(Option<SensorFocus>),
 	}
 }
 
 
-pub fn login() -> Event {
-	Event::Public(event::Public::Login)
+pub fn login(design: String, spawner: Option<String>) -> Event {
+	Event::Public(event::Public::Login(design, spawner))
+}
+
+pub fn respawn() -> Event {
+	Event::Privileged(event::Privileged::Respawn)
 }
 
 pub fn start_broadcast(message: String) -> Event {
@@ -69,6 +113,10 @@ pub fn schedule_maneuver(data: ManeuverData) -> Event {
 	Event::Privileged(event::Privileged::ScheduleManeuver(data))
 }
 
+pub fn update_maneuver(id: EntityId, data: ManeuverData) -> Event {
+	Event::Privileged(event::Privileged::UpdateManeuver(id, data))
+}
+
 pub fn cancel_maneuver(id: EntityId) -> Event {
 	Event::Privileged(event::Privileged::CancelManeuver(id))
 }
@@ -76,3 +124,55 @@ pub fn cancel_maneuver(id: EntityId) -> Event {
 pub fn ftl_jump(destination_time_s: f64) -> Event {
 	Event::Privileged(event::Privileged::FtlJump(destination_time_s))
 }
+
+pub fn eject_cargo(index: usize) -> Event {
+	Event::Privileged(event::Privileged::EjectCargo(index))
+}
+
+pub fn collect_cargo(container_id: EntityId) -> Event {
+	Event::Privileged(event::Privileged::CollectCargo(container_id))
+}
+
+pub fn salvage_wreck(wreck_id: EntityId) -> Event {
+	Event::Privileged(event::Privileged::SalvageWreck(wreck_id))
+}
+
+pub fn dispatch_drones(wreck_id: EntityId) -> Event {
+	Event::Privileged(event::Privileged::DispatchDrones(wreck_id))
+}
+
+pub fn recall_drones() -> Event {
+	Event::Privileged(event::Privileged::RecallDrones)
+}
+
+pub fn dock(station_id: EntityId) -> Event {
+	Event::Privileged(event::Privileged::Dock(station_id))
+}
+
+pub fn undock() -> Event {
+	Event::Privileged(event::Privileged::Undock)
+}
+
+pub fn refuel() -> Event {
+	Event::Privileged(event::Privileged::Refuel)
+}
+
+pub fn repair() -> Event {
+	Event::Privileged(event::Privileged::Repair)
+}
+
+pub fn launch_missile(target_id: EntityId) -> Event {
+	Event::Privileged(event::Privileged::LaunchMissile(target_id))
+}
+
+pub fn set_launcher_mode(mode: LauncherMode) -> Event {
+	Event::Privileged(event::Privileged::SetLauncherMode(mode))
+}
+
+pub fn set_radar(active: bool) -> Event {
+	Event::Privileged(event::Privileged::SetRadar(active))
+}
+
+pub fn set_sensor_focus(focus: Option<SensorFocus>) -> Event {
+	Event::Privileged(event::Privileged::SetSensorFocus(focus))
+}
@@ -1,10 +1,23 @@
 use shared::game::data::{
 	Body,
 	Broadcast,
+	Cargo,
+	Contact,
+	Container,
+	DamageSource,
+	Detection,
+	Docking,
+	DroneBay,
 	EntityId,
+	Hull,
+	Launcher,
 	Maneuver,
+	Missile,
 	Planet,
+	Sensors,
 	Ship,
+	Station,
+	Wreck,
 };
 
 
@@ -14,9 +27,25 @@ pub struct Entity {
 
 	pub body      : Option<Body>,
 	pub broadcast : Option<Broadcast>,
+	pub cargo     : Option<Cargo>,
+	pub container : Option<Container>,
+	pub docking   : Option<Docking>,
+	pub drone_bay : Option<DroneBay>,
+	pub hull      : Option<Hull>,
+	pub launcher  : Option<Launcher>,
 	pub maneuver  : Option<Maneuver>,
+	pub missile   : Option<Missile>,
 	pub planet    : Option<Planet>,
+	pub sensors   : Option<Sensors>,
 	pub ship      : Option<Ship>,
+	pub station   : Option<Station>,
+	pub wreck     : Option<Wreck>,
+
+	/// How well the receiving client's ship detects the entity. This is
+	/// `None` for the client's own ship and for things that are always known,
+	/// like planets. Entities that are not identified are sent as contacts
+	/// instead.
+	pub detection: Option<Detection>,
 }
 
 
@@ -26,4 +55,20 @@ pub enum Event {
     ShipId(EntityId),
     UpdateEntity(Entity),
     RemoveEntity(EntityId),
+
+    /// Replaces `UpdateEntity` for entities that the client's ship detects,
+    /// but can't identify.
+    UpdateContact(EntityId, Contact),
+
+    /// An event sent by the client has been rejected. Contains the reason.
+    Rejected(String),
+
+    FtlJumpAborted(String),
+
+    /// The player's ship has collided with another object. Contains the other
+    /// object and the damage the ship has taken.
+    Collided(EntityId, f64),
+
+    /// The player's ship has been destroyed. Contains what destroyed it.
+    ShipDestroyed(DamageSource),
 }
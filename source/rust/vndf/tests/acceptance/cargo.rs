@@ -0,0 +1,33 @@
+use vndf::client::interface::InputEvent;
+use vndf::server::game::initial_state::InitialState;
+use vndf::testing::rc;
+
+
+#[test]
+fn players_should_be_able_to_eject_and_collect_cargo() {
+    let     server = rc::Server::start(InitialState::new());
+    let mut client = rc::Client::start(server.port());
+
+    let frame = client.wait_until(|frame| {
+        match frame.cargo {
+            Some(ref cargo) => cargo.items.len() > 0,
+            None            => false,
+        }
+    });
+    let items = frame.cargo.unwrap().items.len();
+
+    client.input(InputEvent::EjectCargo(0));
+
+    let frame = client.wait_until(|frame| {
+        frame.containers.len() == 1 &&
+            frame.cargo.as_ref().unwrap().items.len() == items - 1
+    });
+
+    let container_id = *frame.containers.keys().next().unwrap();
+    client.input(InputEvent::CollectCargo(container_id));
+
+    client.wait_until(|frame| {
+        frame.containers.len() == 0 &&
+            frame.cargo.as_ref().unwrap().items.len() == items
+    });
+}
@@ -36,6 +36,7 @@ fn a_ship_colliding_with_a_celestial_body_should_be_removed() {
 		.with_spawner(Spawner {
 			position: Vec2::new(-15.0, 0.0),
 			velocity: Vec2::new(1.0, 0.0),
+			.. Spawner::new()
 		});
 
 	let     server = rc::Server::start(initial_state);
@@ -62,6 +63,7 @@ fn a_celestial_body_should_exert_gravitational_influence_on_ships() {
 		.with_spawner(Spawner {
 			position: Vec2::new(-15.0, 0.0),
 			velocity: Vec2::new(0.0, 0.0),
+			.. Spawner::new()
 		});
 
 	let     server = rc::Server::start(initial_state);
@@ -11,7 +11,8 @@ fn players_should_be_able_to_perform_ftl_jumps() {
     let initial_state = InitialState::new()
         .with_spawner(Spawner {
             position: Vec2::new(0.0, 0.0),
-            velocity: Vec2::new(1.0, 0.0),
+            velocity: Vec2::new(100.0, 0.0),
+            .. Spawner::new()
         });
 
     let     server = rc::Server::start(initial_state);
@@ -21,10 +22,10 @@ fn players_should_be_able_to_perform_ftl_jumps() {
         frame.own_ship().is_some()
     });
 
-    let jump_destination_s = frame.game_time_s.unwrap() + 1000000.0;
+    let jump_destination_s = frame.game_time_s.unwrap() + 3000.0;
     client.input(InputEvent::FtlJump(jump_destination_s));
 
     client.wait_until(|frame| {
-        frame.own_ship().unwrap().position.x >= 1000000.0
+        frame.own_ship().unwrap().position.x > 300000.0
     });
 }
@@ -2,7 +2,10 @@ use std::f64::consts::PI;
 
 use vndf::server::game::initial_state::InitialState;
 use vndf::client::interface::InputEvent;
-use vndf::shared::game::data::ManeuverData;
+use vndf::shared::game::data::{
+    ManeuverData,
+    Orientation,
+};
 use vndf::shared::util::{
     angle_of,
     is_point_on_line,
@@ -81,10 +84,11 @@ fn it_should_schedule_maneuvers() {
     let maneuver_direction_rad = velocity_direction_rad + PI / 2.0;
 
     let data = ManeuverData {
-        start_s   : frame.game_time_s.unwrap(),
-        duration_s: 1.0,
-        angle     : maneuver_direction_rad,
-        thrust    : 1.0,
+        start_s    : frame.game_time_s.unwrap(),
+        duration_s : 1.0,
+        angle      : maneuver_direction_rad,
+        thrust     : 1.0,
+        orientation: Orientation::Fixed,
     };
 
     client.input(InputEvent::ScheduleManeuver(data));
@@ -115,10 +119,11 @@ fn scheduled_maneuvers_should_be_visible() {
     });
 
     let maneuver_data = ManeuverData {
-        start_s   : frame.game_time_s.unwrap() + 1000.0,
-        duration_s: 1.0,
-        angle     : 0.0,
-        thrust    : 1.0,
+        start_s    : frame.game_time_s.unwrap() + 1000.0,
+        duration_s : 1.0,
+        angle      : 0.0,
+        thrust     : 1.0,
+        orientation: Orientation::Fixed,
     };
 
     client.input(InputEvent::ScheduleManeuver(maneuver_data));
@@ -153,10 +158,11 @@ fn finished_maneuvers_should_be_removed() {
     });
 
     let data = ManeuverData {
-        start_s   : frame.game_time_s.unwrap() + 0.1,
-        duration_s: 0.1,
-        angle     : 0.0,
-        thrust    : 1.0,
+        start_s    : frame.game_time_s.unwrap() + 0.1,
+        duration_s : 0.1,
+        angle      : 0.0,
+        thrust     : 1.0,
+        orientation: Orientation::Fixed,
     };
 
     client.input(InputEvent::ScheduleManeuver(data));
@@ -179,10 +185,11 @@ fn players_should_be_able_to_cancel_maneuvers() {
     });
 
     let data = ManeuverData {
-        start_s   : frame.game_time_s.unwrap() + 1000.0,
-        duration_s: 1000.0,
-        angle     : 0.0,
-        thrust    : 1.0,
+        start_s    : frame.game_time_s.unwrap() + 1000.0,
+        duration_s : 1000.0,
+        angle      : 0.0,
+        thrust     : 1.0,
+        orientation: Orientation::Fixed,
     };
 
     client.input(InputEvent::ScheduleManeuver(data));
@@ -209,10 +216,11 @@ fn players_should_only_see_their_own_maneuvers() {
     });
 
     let data = ManeuverData {
-        start_s   : frame.game_time_s.unwrap() + 1000.0,
-        duration_s: 1.0,
-        angle     : 0.0,
-        thrust    : 1.0,
+        start_s    : frame.game_time_s.unwrap() + 1000.0,
+        duration_s : 1.0,
+        angle      : 0.0,
+        thrust     : 1.0,
+        orientation: Orientation::Fixed,
     };
 
     client_a.input(InputEvent::ScheduleManeuver(data));
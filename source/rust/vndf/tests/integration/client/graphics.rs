@@ -7,6 +7,7 @@ use vndf::shared::game::data::{
 	Body,
 	Ship,
 };
+use vndf::shared::game::design::DEFAULT_DESIGN;
 use vndf::shared::protocol::{
 	client,
 	server,
@@ -26,7 +27,7 @@ fn it_should_interpolate_between_snapshots_sent_by_server() {
 
 	let event = server.wait_until(|event|
 		if let &mut Some((_, ref event)) = event {
-			*event == client::Event::Public(client::event::Public::Login)
+			*event == client::login(DEFAULT_DESIGN.to_string(), None)
 		}
 		else {
 			false
@@ -48,11 +49,26 @@ fn it_should_interpolate_between_snapshots_sent_by_server() {
 			force   : Vec2::new(0.0, 0.0),
 			mass    : 0.0,
 		}),
-		ship: Some(Ship),
+		ship: Some(Ship {
+			thrust: 1.0,
+			radius: 10.0,
+		}),
 
 		broadcast: None,
+		cargo    : None,
+		container: None,
+		docking  : None,
+		drone_bay: None,
+		hull     : None,
+		launcher : None,
 		maneuver : None,
+		missile  : None,
 		planet   : None,
+		sensors  : None,
+		station  : None,
+		wreck    : None,
+
+		detection: None,
 	};
 
 	server.send(address, server::Event::Heartbeat(1.0));
@@ -1,3 +1,4 @@
+use vndf::shared::game::design::DEFAULT_DESIGN;
 use vndf::shared::protocol::{
 	client,
 	server,
@@ -15,7 +16,7 @@ fn it_should_display_an_error_if_connection_to_server_is_lost() {
 
 	let event = server.wait_until(|event|
 		if let &mut Some((_, ref event)) = event {
-			event == &client::Event::Public(client::event::Public::Login)
+			event == &client::login(DEFAULT_DESIGN.to_string(), None)
 		}
 		else {
 			false
@@ -1,10 +1,12 @@
 use vndf::server::game::initial_state::InitialState;
+use vndf::shared::game::design::DEFAULT_DESIGN;
 use vndf::shared::protocol::client::{
 	login,
 	start_broadcast,
 };
 use vndf::shared::protocol::server::Event::{
 	Heartbeat,
+	Rejected,
 	ShipId,
 };
 use vndf::testing::{
@@ -28,7 +30,7 @@ fn it_should_ignore_clients_that_havent_logged_in() {
 	// that case is more realistic, and that's what this test is about.
 	// Let's make sure it still works by logging in with a second client.
 
-	client_2.send(login());
+	client_2.send(login(DEFAULT_DESIGN.to_string(), None));
 	client_2.wait_until(|event| {
 		match event {
 			&Some(Heartbeat(_)) => true,
@@ -42,7 +44,7 @@ fn it_should_ignore_duplicate_logins() {
 	let     server = rc::Server::start(InitialState::new());
 	let mut client = mock::Client::start(server.port());
 
-	client.send(login());
+	client.send(login(DEFAULT_DESIGN.to_string(), None));
 
 	let mut first_ship_id = None;
 	client.wait_until(|event| {
@@ -55,7 +57,7 @@ fn it_should_ignore_duplicate_logins() {
 		}
 	});
 
-	client.send(login());
+	client.send(login(DEFAULT_DESIGN.to_string(), None));
 	client.send(start_broadcast("This is a broadcast.".to_string()));
 
 	let mut second_ship_id = None;
@@ -88,12 +90,28 @@ fn it_should_ignore_duplicate_logins() {
 	}
 }
 
+#[test]
+fn it_should_reject_logins_with_unknown_ship_designs() {
+	let     server = rc::Server::start(InitialState::new());
+	let mut client = mock::Client::start(server.port());
+
+	client.send(login("no such design".to_string(), None));
+
+	client.wait_until(|event| {
+		match *event {
+			Some(Rejected(_)) => true,
+			Some(ShipId(_))   => panic!("Unexpected login"),
+			_                 => false,
+		}
+	});
+}
+
 #[test]
 fn it_should_send_regular_heartbeats_with_current_game_time() {
 	let     server = rc::Server::start(InitialState::new());
 	let mut client = mock::Client::start(server.port());
 
-	client.send(login());
+	client.send(login(DEFAULT_DESIGN.to_string(), None));
 
 	let game_time_1_s = receive_heartbeat(&mut client);
 	let game_time_2_s = receive_heartbeat(&mut client);
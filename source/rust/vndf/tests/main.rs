@@ -10,9 +10,11 @@ mod unit {
     }
     mod shared {
         mod color;
+        mod navigation;
     }
     mod physics {
         mod collision;
+        mod spatial_index;
     }
 }
 
@@ -28,6 +30,7 @@ mod integration {
 }
 mod acceptance {
     mod basic;
+    mod cargo;
     mod celestials;
     mod ftl;
     mod navigation;
@@ -0,0 +1,59 @@
+use std::f64;
+
+use nalgebra::Vec2;
+use vndf::shared::physics::SpatialIndex;
+
+#[test]
+fn neighbours() {
+    let mut index = SpatialIndex::new(10.0);
+
+    index.insert(0, Vec2::new(0.0, 0.0));
+    index.insert(1, Vec2::new(5.0, 5.0));
+    index.insert(2, Vec2::new(-15.0, 0.0));
+    index.insert(3, Vec2::new(100.0, 100.0));
+
+    let mut neighbours = index.neighbours(Vec2::new(0.0, 0.0), 15.0);
+    neighbours.sort();
+    assert_eq!(neighbours, vec![0, 1, 2]);
+
+    // Entities in a cell that is touched by the query, but that are too far
+    // away, aren't neighbours.
+    let mut neighbours = index.neighbours(Vec2::new(0.0, 0.0), 10.0);
+    neighbours.sort();
+    assert_eq!(neighbours, vec![0, 1]);
+
+    assert_eq!(index.neighbours(Vec2::new(100.0, 95.0), 5.0), vec![3]);
+
+    // Huge queries don't iterate over every cell they cover.
+    let mut neighbours = index.neighbours(Vec2::new(0.0, 0.0), 1e300);
+    neighbours.sort();
+    assert_eq!(neighbours, vec![0, 1, 2, 3]);
+
+    index.clear();
+    assert!(index.neighbours(Vec2::new(0.0, 0.0), 15.0).is_empty());
+}
+
+#[test]
+fn neighbours_at_extreme_positions() {
+    let mut index = SpatialIndex::new(10.0);
+
+    index.insert(0, Vec2::new(1e300, -1e300));
+    index.insert(1, Vec2::new(f64::NAN, 0.0));
+    index.insert(2, Vec2::new(0.0, f64::INFINITY));
+
+    // Occupy enough cells, so the queries below look at the cells they cover.
+    for id in 3 .. 10 {
+        index.insert(id, Vec2::new(id as f64 * 100.0, 0.0));
+    }
+
+    // Entities beyond the edge of the grid can still be found.
+    assert_eq!(index.neighbours(Vec2::new(1e300, -1e300), 5.0), vec![0]);
+
+    // Non-finite positions are neither indexed nor queried.
+    let mut neighbours =
+        index.neighbours(Vec2::new(0.0, 0.0), f64::INFINITY);
+    neighbours.sort();
+    assert_eq!(neighbours, vec![0, 3, 4, 5, 6, 7, 8, 9]);
+    assert!(index.neighbours(Vec2::new(f64::NAN, 0.0), 5.0).is_empty());
+    assert!(index.neighbours(Vec2::new(0.0, 0.0), f64::NAN).is_empty());
+}
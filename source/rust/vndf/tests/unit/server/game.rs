@@ -1,14 +1,52 @@
-use nalgebra::Vec2;
+use std::f64;
+use std::f64::consts::PI;
 
+use nalgebra::{
+    Dot,
+    Norm,
+    Vec2,
+};
+
+use vndf::server::game::data::Spawner;
 use vndf::server::game::events;
+use vndf::server::game::initial_state::{
+    Celestial,
+    InitialState,
+    OrbitSpawner,
+    StationSite,
+    Swarm,
+};
 use vndf::server::game::state::{
     GameEvent,
     GameState,
 };
 use vndf::shared::game::data::{
     Body,
+    CargoKind,
+    Classification,
+    DamageSource,
+    DetectionQuality,
     EntityId,
+    LauncherMode,
     ManeuverData,
+    Orientation,
+    Planet,
+    SensorFocus,
+};
+use vndf::shared::game::design::{
+    ModuleKind,
+    ShipDesign,
+};
+use vndf::shared::game::logic::{
+    is_inside,
+    GRAVITATIONAL_CONSTANT,
+};
+use vndf::shared::protocol::server::Event::{
+    Collided,
+    FtlJumpAborted,
+    ShipDestroyed,
+    UpdateContact,
+    UpdateEntity,
 };
 use vndf::shared::util::angle_of;
 
@@ -17,19 +55,21 @@ use vndf::shared::util::angle_of;
 fn it_should_execute_multiple_maneuvers_after_each_other() {
     let mut game_state = GameState::new(0.0);
 
-    let ship_id = game_state.handle_event(events::Enter).unwrap();
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
 
     let maneuver_a = ManeuverData {
-        start_s   : 0.5,
-        duration_s: 0.05,
-        angle     : 1.0,
-        thrust    : 1.0,
+        start_s    : 0.5,
+        duration_s : 0.05,
+        angle      : 1.0,
+        thrust     : 1.0,
+        orientation: Orientation::Fixed,
     };
     let maneuver_b = ManeuverData {
-        start_s   : 1.0,
-        duration_s: 0.05,
-        angle     : -1.0,
-        thrust    : 1.0,
+        start_s    : 1.0,
+        duration_s : 0.05,
+        angle      : -1.0,
+        thrust     : 1.0,
+        orientation: Orientation::Fixed,
     };
 
     game_state
@@ -66,13 +106,14 @@ fn it_should_execute_multiple_maneuvers_after_each_other() {
 fn maneuvers_should_apply_thrust_over_time() {
     let mut game_state = GameState::new(0.0);
 
-    let ship_id = game_state.handle_event(events::Enter).unwrap();
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
 
     let maneuver = ManeuverData {
-        start_s   : 0.5,
-        duration_s: 0.2,
-        angle     : 1.0,
-        thrust    : 1.0,
+        start_s    : 0.5,
+        duration_s : 0.2,
+        angle      : 1.0,
+        thrust     : 1.0,
+        orientation: Orientation::Fixed,
     };
 
     game_state
@@ -108,24 +149,26 @@ fn maneuvers_should_apply_thrust_over_time() {
 fn maneuver_thrust_should_be_configurable() {
     let mut game_state = GameState::new(0.0);
 
-    let ship_id_a = game_state.handle_event(events::Enter).unwrap();
-    let ship_id_b = game_state.handle_event(events::Enter).unwrap();
+    let ship_id_a = game_state.handle_event(events::Enter::new()).unwrap();
+    let ship_id_b = game_state.handle_event(events::Enter::new()).unwrap();
 
     let start_s    = 0.5;
     let duration_s = 1.0;
     let angle      = 0.0;
 
     let maneuver_a = ManeuverData {
-        start_s   : start_s,
-        duration_s: duration_s,
-        angle     : angle,
-        thrust    : 1.0,
+        start_s    : start_s,
+        duration_s : duration_s,
+        angle      : angle,
+        thrust     : 1.0,
+        orientation: Orientation::Fixed,
     };
     let maneuver_b = ManeuverData {
-        start_s   : start_s,
-        duration_s: duration_s,
-        angle     : angle,
-        thrust    : 0.5,
+        start_s    : start_s,
+        duration_s : duration_s,
+        angle      : angle,
+        thrust     : 0.5,
+        orientation: Orientation::Fixed,
     };
 
     game_state
@@ -150,6 +193,137 @@ fn maneuver_thrust_should_be_configurable() {
     assert!(body_a.velocity.x > body_b.velocity.x);
 }
 
+#[test]
+fn prograde_maneuvers_should_thrust_along_the_velocity() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+    game_state.entities.bodies.get_mut(&ship_id).unwrap().velocity =
+        Vec2::new(0.0, 10.0);
+
+    game_state
+        .handle_event(events::ScheduleManeuver {
+            ship_id: ship_id,
+            data   : ManeuverData {
+                start_s    : 0.0,
+                duration_s : 1.0,
+                angle      : 0.0, // would thrust along the x axis, if fixed
+                thrust     : 1.0,
+                orientation: Orientation::Prograde,
+            },
+        })
+        .unwrap();
+
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let body = get_body(ship_id, &mut game_state);
+    assert!(body.velocity.x.abs() < 1e-9);
+    assert!(body.velocity.y > 10.0);
+}
+
+#[test]
+fn maneuvers_should_be_able_to_follow_a_target() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&ship_id).unwrap().velocity =
+        Vec2::new(0.0, 0.0);
+    {
+        let target = game_state.entities.bodies.get_mut(&target_id).unwrap();
+        target.position = Vec2::new(1000.0, 0.0);
+        target.velocity = Vec2::new(0.0, 10.0);
+    }
+
+    // The ship's sensors need to pick up the target first.
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+
+    game_state
+        .handle_event(events::ScheduleManeuver {
+            ship_id: ship_id,
+            data   : ManeuverData {
+                start_s    : 0.0,
+                duration_s : 1.0,
+                angle      : 0.0,
+                thrust     : 1.0,
+                orientation: Orientation::Target(target_id),
+            },
+        })
+        .unwrap();
+
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    // The ship heads straight for the target.
+    let body = get_body(ship_id, &mut game_state);
+    assert!(body.velocity.x > 0.0);
+    assert!(body.velocity.y.abs() < body.velocity.x * 0.01);
+}
+
+#[test]
+fn maneuvers_should_be_able_to_match_the_velocity_of_a_target() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&ship_id).unwrap().velocity =
+        Vec2::new(0.0, 0.0);
+    {
+        let target = game_state.entities.bodies.get_mut(&target_id).unwrap();
+        target.position = Vec2::new(1000.0, 0.0);
+        target.velocity = Vec2::new(0.0, 10.0);
+    }
+
+    // The ship's sensors need to pick up the target first.
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+
+    game_state
+        .handle_event(events::ScheduleManeuver {
+            ship_id: ship_id,
+            data   : ManeuverData {
+                start_s    : 0.0,
+                duration_s : 1.0,
+                angle      : 0.0,
+                thrust     : 1.0,
+                orientation: Orientation::MatchVelocity(target_id),
+            },
+        })
+        .unwrap();
+
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    // The ship matches the target's velocity, instead of heading for it.
+    let body = get_body(ship_id, &mut game_state);
+    assert!(body.velocity.x.abs() < 1e-9);
+    assert!(body.velocity.y > 0.0);
+}
+
+#[test]
+fn maneuvers_should_only_refer_to_detected_entities() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&target_id).unwrap().position =
+        Vec2::new(1e9, 0.0);
+
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+
+    let result = game_state.handle_event(events::ScheduleManeuver {
+        ship_id: ship_id,
+        data   : ManeuverData {
+            start_s    : 0.0,
+            duration_s : 1.0,
+            angle      : 0.0,
+            thrust     : 1.0,
+            orientation: Orientation::RadialIn(target_id),
+        },
+    });
+    assert!(result.is_err());
+}
+
 #[test]
 fn game_state_should_reject_invalid_events() {
     struct InvalidEvent;
@@ -157,8 +331,8 @@ fn game_state_should_reject_invalid_events() {
     impl GameEvent for InvalidEvent {
         type Output = ();
 
-        fn validate(&self, _: &GameState) -> bool {
-            false
+        fn validate(&self, _: &GameState) -> Result<(), String> {
+            Err(format!("This event is always invalid"))
         }
         fn execute(self, game_state: &mut GameState) {
             game_state.entities
@@ -186,41 +360,105 @@ fn maneuver_thrust_should_be_validated() {
         ship_id: 0,
 
         data: ManeuverData {
-            start_s   : 0.0,
-            duration_s: 1.0,
-            angle     : 0.0,
-            thrust    : 1.01,
+            start_s    : 0.0,
+            duration_s : 1.0,
+            angle      : 0.0,
+            thrust     : 1.01,
+            orientation: Orientation::Fixed,
         }
     };
     let thrust_below_min = events::ScheduleManeuver {
         ship_id: 0,
 
         data: ManeuverData {
-            start_s   : 0.0,
-            duration_s: 1.0,
-            angle     : 0.0,
-            thrust    : -0.01,
+            start_s    : 0.0,
+            duration_s : 1.0,
+            angle      : 0.0,
+            thrust     : -0.01,
+            orientation: Orientation::Fixed,
         }
     };
 
     let game_state = GameState::new(0.0);
 
-    assert_eq!(thrust_above_max.validate(&game_state), false);
-    assert_eq!(thrust_below_min.validate(&game_state), false);
+    assert!(thrust_above_max.validate(&game_state).is_err());
+    assert!(thrust_below_min.validate(&game_state).is_err());
+}
+
+#[test]
+fn maneuver_timing_should_be_validated() {
+    let game_state = GameState::new(100.0);
+
+    let valid = |start_s, duration_s| {
+        schedule_maneuver(0, start_s, duration_s)
+            .validate(&game_state)
+            .is_ok()
+    };
+
+    assert!(valid(100.0, 1.0));
+    assert!(!valid(10.0, 1.0));
+    assert!(!valid(100.0, 0.0));
+    assert!(!valid(100.0, -1.0));
+    assert!(!valid(f64::NAN, 1.0));
+
+    let mut nan_thrust = schedule_maneuver(0, 100.0, 1.0);
+    nan_thrust.data.thrust = f64::NAN;
+    assert!(nan_thrust.validate(&game_state).is_err());
+
+    let mut nan_angle = schedule_maneuver(0, 100.0, 1.0);
+    nan_angle.data.angle = f64::NAN;
+    assert!(nan_angle.validate(&game_state).is_err());
+}
+
+#[test]
+fn overlapping_maneuvers_should_be_rejected() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    {
+        let mut schedule = |start_s| {
+            game_state.handle_event(schedule_maneuver(ship_id, start_s, 1.0))
+        };
+
+        schedule(1.0).unwrap();
+
+        assert!(schedule(1.5).is_err());
+        assert!(schedule(0.5).is_err());
+        assert!(schedule(2.0).is_ok());
+    }
+    assert_eq!(game_state.entities.maneuvers.len(), 2);
+}
+
+#[test]
+fn the_number_of_scheduled_maneuvers_should_be_limited() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    for i in 0 .. events::MAX_QUEUED_MANEUVERS {
+        let maneuver = schedule_maneuver(ship_id, i as f64 * 10.0, 1.0);
+        game_state.handle_event(maneuver).unwrap();
+    }
+
+    let start_s  = events::MAX_QUEUED_MANEUVERS as f64 * 10.0;
+    let maneuver = schedule_maneuver(ship_id, start_s, 1.0);
+    assert!(game_state.handle_event(maneuver).is_err());
 }
 
 #[test]
 fn players_should_only_be_able_to_cancel_their_own_maneuvers() {
     let mut game_state = GameState::new(0.0);
 
-    let ship_id_a = game_state.handle_event(events::Enter).unwrap();
-    let ship_id_b = game_state.handle_event(events::Enter).unwrap();
+    let ship_id_a = game_state.handle_event(events::Enter::new()).unwrap();
+    let ship_id_b = game_state.handle_event(events::Enter::new()).unwrap();
 
     let maneuver = ManeuverData {
-        start_s   : 0.5,
-        duration_s: 1.0,
-        angle     : 0.0,
-        thrust    : 1.0,
+        start_s    : 0.5,
+        duration_s : 1.0,
+        angle      : 0.0,
+        thrust     : 1.0,
+        orientation: Orientation::Fixed,
     };
 
     game_state
@@ -250,23 +488,1517 @@ fn players_should_only_be_able_to_cancel_their_own_maneuvers() {
 }
 
 #[test]
-fn updates_should_update_the_game_time() {
+fn maneuvers_should_be_updated_in_place() {
     let mut game_state = GameState::new(0.0);
 
-    let now_s = 10.0;
-    game_state.handle_event(events::Update { now_s: now_s }).unwrap();
-    assert_eq!(game_state.time_s, now_s);
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    let maneuver = ManeuverData {
+        start_s    : 10.0,
+        duration_s : 5.0,
+        angle      : 0.0,
+        thrust     : 1.0,
+        orientation: Orientation::Fixed,
+    };
+    game_state
+        .handle_event(events::ScheduleManeuver {
+            ship_id: ship_id,
+            data   : maneuver,
+        })
+        .unwrap();
+
+    let maneuver_id = get_maneuver_id(ship_id, &mut game_state);
+
+    // The new timing overlaps the old one. That's fine, as it replaces it.
+    let updated = ManeuverData {
+        start_s    : 12.0,
+        duration_s : 4.0,
+        angle      : 1.0,
+        thrust     : 0.5,
+        .. maneuver
+    };
+    game_state
+        .handle_event(events::UpdateManeuver {
+            ship_id    : ship_id,
+            maneuver_id: maneuver_id,
+            data       : updated,
+        })
+        .unwrap();
+
+    assert_eq!(game_state.entities.maneuvers.len(), 1);
+    assert_eq!(game_state.entities.maneuvers[&maneuver_id].data, updated);
+
+    let invalid = ManeuverData {
+        duration_s: -1.0,
+        .. maneuver
+    };
+    assert!(
+        game_state
+            .handle_event(events::UpdateManeuver {
+                ship_id    : ship_id,
+                maneuver_id: maneuver_id,
+                data       : invalid,
+            })
+            .is_err()
+    );
 }
 
+#[test]
+fn started_maneuvers_should_not_be_updated() {
+    let mut game_state = GameState::new(0.0);
 
-fn get_body(body_id: EntityId, game_state: &mut GameState) -> Body {
-    for entity in game_state.export_entities() {
-        if entity.id == body_id {
-            return entity.body.unwrap();
+    let ship_id_a = game_state.handle_event(events::Enter::new()).unwrap();
+    let ship_id_b = game_state.handle_event(events::Enter::new()).unwrap();
+
+    let maneuver = ManeuverData {
+        start_s    : 0.5,
+        duration_s : 10.0,
+        angle      : 0.0,
+        thrust     : 1.0,
+        orientation: Orientation::Fixed,
+    };
+    game_state
+        .handle_event(events::ScheduleManeuver {
+            ship_id: ship_id_a,
+            data   : maneuver,
+        })
+        .unwrap();
+
+    let maneuver_id = get_maneuver_id(ship_id_a, &mut game_state);
+
+    let update = |ship_id| {
+        events::UpdateManeuver {
+            ship_id    : ship_id,
+            maneuver_id: maneuver_id,
+            data       : ManeuverData { thrust: 0.5, .. maneuver },
+        }
+    };
+
+    assert!(update(ship_id_b).validate(&game_state).is_err());
+    assert!(update(ship_id_a).validate(&game_state).is_ok());
+
+    game_state.handle_event(events::Update { now_s: 1.0 }).unwrap();
+
+    assert!(update(ship_id_a).validate(&game_state).is_err());
+}
+
+#[test]
+fn ftl_jumps_should_be_validated() {
+    let mut game_state = GameState::new(100.0);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    let into_the_past = events::FtlJump {
+        ship_id      : ship_id,
+        target_time_s: 50.0,
+    };
+    let max_jump_s = game_state.entities.ftl_drives[&ship_id].max_jump_s;
+    let too_far = events::FtlJump {
+        ship_id      : ship_id,
+        target_time_s: 100.0 + max_jump_s + 1.0,
+    };
+    let no_ship = events::FtlJump {
+        ship_id      : ship_id + 1,
+        target_time_s: 200.0,
+    };
+    let nan = events::FtlJump {
+        ship_id      : ship_id,
+        target_time_s: f64::NAN,
+    };
+
+    assert!(into_the_past.validate(&game_state).is_err());
+    assert!(too_far.validate(&game_state).is_err());
+    assert!(no_ship.validate(&game_state).is_err());
+    assert!(nan.validate(&game_state).is_err());
+}
+
+#[test]
+fn ftl_jumps_should_require_the_drive_to_recharge() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state
+        .handle_event(events::FtlJump {
+            ship_id      : ship_id,
+            target_time_s: 1000.0,
+        })
+        .unwrap();
+
+    let result = game_state.handle_event(events::FtlJump {
+        ship_id      : ship_id,
+        target_time_s: 2000.0,
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn ftl_jumps_should_use_up_fuel() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+    let before  = game_state.entities.ftl_drives[&ship_id];
+
+    game_state
+        .handle_event(events::FtlJump {
+            ship_id      : ship_id,
+            target_time_s: 1000.0,
+        })
+        .unwrap();
+
+    let after = game_state.entities.ftl_drives[&ship_id];
+    assert_eq!(after.fuel_s, before.fuel_s - 1000.0);
+
+    game_state.entities.ftl_drives.get_mut(&ship_id).unwrap().fuel_s = 500.0;
+    game_state.handle_event(events::Update { now_s: 1100.0 }).unwrap();
+
+    let result = game_state.handle_event(events::FtlJump {
+        ship_id      : ship_id,
+        target_time_s: 2100.0,
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn ftl_jumps_should_be_aborted_before_hitting_a_planet() {
+    let mut game_state = GameState::new(0.0);
+
+    let planet = Planet {
+        position: Vec2::new(5000.0, 0.0),
+        radius  : 1000.0,
+        mass    : 1.0,
+        color   : [1.0, 1.0, 1.0],
+    };
+    game_state.entities.create_entity().with_planet(planet);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    // The planet's surface is hit in the middle of a step of the jump
+    game_state.entities.bodies.get_mut(&ship_id).unwrap().velocity =
+        Vec2::new(15.0, 0.0);
+
+    game_state
+        .handle_event(events::FtlJump {
+            ship_id      : ship_id,
+            target_time_s: 1000.0,
+        })
+        .unwrap();
+
+    let body = game_state.entities.bodies[&ship_id];
+    assert!(!is_inside(&body, &planet));
+    assert!(body.position.x < planet.position.x);
+
+    // The jump is aborted close to the surface, not at the start of the step
+    let surface_x = planet.position.x - planet.radius;
+    assert!(surface_x - body.position.x < 100.0);
+
+    let mut aborted = false;
+    for (id, event) in game_state.notifications() {
+        if let FtlJumpAborted(_) = event {
+            aborted = id == ship_id;
         }
     }
+    assert!(aborted);
+}
 
-    unreachable!();
+#[test]
+fn ftl_jumps_should_not_skip_small_planets() {
+    let mut game_state = GameState::new(0.0);
+
+    game_state.entities.create_entity().with_planet(Planet {
+        position: Vec2::new(50000.0, 0.0),
+        radius  : 10.0,
+        mass    : 1.0,
+        color   : [1.0, 1.0, 1.0],
+    });
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    // Fast enough to pass the planet between two steps of the jump
+    game_state.entities.bodies.get_mut(&ship_id).unwrap().velocity =
+        Vec2::new(1000.0, 0.0);
+
+    game_state
+        .handle_event(events::FtlJump {
+            ship_id      : ship_id,
+            target_time_s: 1000.0,
+        })
+        .unwrap();
+
+    assert!(game_state.entities.bodies[&ship_id].position.x < 50000.0);
+}
+
+#[test]
+fn fast_ships_should_not_pass_through_planets() {
+    let mut game_state = GameState::new(0.0);
+
+    game_state.entities.create_entity().with_planet(Planet {
+        position: Vec2::new(100.0, 0.0),
+        radius  : 10.0,
+        mass    : 1.0,
+        color   : [1.0, 1.0, 1.0],
+    });
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&ship_id).unwrap().velocity =
+        Vec2::new(1000.0, 0.0);
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    assert!(!game_state.entities.bodies.contains_key(&ship_id));
+}
+
+#[test]
+fn fast_ships_should_not_pass_through_each_other() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id_a = game_state.handle_event(events::Enter::new()).unwrap();
+    let ship_id_b = game_state.handle_event(events::Enter::new()).unwrap();
+
+    {
+        let body = game_state.entities.bodies.get_mut(&ship_id_b).unwrap();
+        body.position = Vec2::new(300.0, 0.0);
+        body.velocity = Vec2::new(-1000.0, 0.0);
+    }
+
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let mut collided = false;
+    for (id, event) in game_state.notifications() {
+        match event {
+            Collided(_, _) | ShipDestroyed(_) => collided |= id == ship_id_a,
+            _                                 => (),
+        }
+    }
+    assert!(collided);
+}
+
+#[test]
+fn ejected_cargo_should_become_a_container_with_the_ships_velocity() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+    let before  = get_body(ship_id, &mut game_state);
+
+    let container_id = game_state
+        .handle_event(events::EjectCargo {
+            ship_id: ship_id,
+            index  : 0,
+        })
+        .unwrap();
+
+    let ship      = get_body(ship_id, &mut game_state);
+    let container = get_body(container_id, &mut game_state);
+
+    assert_eq!(container.velocity, before.velocity);
+    assert_eq!(ship.mass + container.mass, before.mass);
+    assert!(game_state.entities.containers.contains_key(&container_id));
+    assert_eq!(game_state.entities.cargo_holds[&ship_id].items.len(), 0);
+}
+
+#[test]
+fn ejecting_nonexistent_cargo_should_be_rejected() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    let result = game_state.handle_event(events::EjectCargo {
+        ship_id: ship_id,
+        index  : 100,
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn ships_should_collect_containers_within_range() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id_a = game_state.handle_event(events::Enter::new()).unwrap();
+    let ship_id_b = game_state.handle_event(events::Enter::new()).unwrap();
+    let before    = get_body(ship_id_b, &mut game_state);
+
+    let container_id = game_state
+        .handle_event(events::EjectCargo {
+            ship_id: ship_id_a,
+            index  : 0,
+        })
+        .unwrap();
+
+    // Make room for the collected cargo.
+    game_state
+        .handle_event(events::EjectCargo {
+            ship_id: ship_id_b,
+            index  : 0,
+        })
+        .unwrap();
+
+    // The ship's sensors need to pick up the container first.
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+
+    game_state
+        .handle_event(events::CollectCargo {
+            ship_id     : ship_id_b,
+            container_id: container_id,
+        })
+        .unwrap();
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+
+    assert!(!game_state.entities.entities.contains(&container_id));
+    assert_eq!(game_state.entities.cargo_holds[&ship_id_b].items.len(), 1);
+    assert_eq!(get_body(ship_id_b, &mut game_state).mass, before.mass);
+}
+
+#[test]
+fn containers_out_of_range_should_not_be_collectable() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id_a = game_state.handle_event(events::Enter::new()).unwrap();
+    let ship_id_b = game_state.handle_event(events::Enter::new()).unwrap();
+
+    let container_id = game_state
+        .handle_event(events::EjectCargo {
+            ship_id: ship_id_a,
+            index  : 0,
+        })
+        .unwrap();
+
+    game_state.entities.bodies.get_mut(&ship_id_b).unwrap().position =
+        Vec2::new(events::COLLECT_RANGE * 2.0, 0.0);
+
+    // The ship's sensors need to pick up the container first.
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+
+    let result = game_state.handle_event(events::CollectCargo {
+        ship_id     : ship_id_b,
+        container_id: container_id,
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn launching_a_missile_should_use_up_one_from_the_launcher() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id = game_state.handle_event(events::Enter::new()).unwrap();
+    let before    = game_state.entities.launchers[&ship_id];
+
+    game_state.entities.bodies.get_mut(&target_id).unwrap().position =
+        Vec2::new(500.0, 200.0);
+
+    // The ship's sensors need to pick up the target first.
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+
+    let missile_id = game_state
+        .handle_event(events::LaunchMissile {
+            ship_id  : ship_id,
+            target_id: target_id,
+        })
+        .unwrap();
+
+    let after = game_state.entities.launchers[&ship_id];
+    assert_eq!(after.missiles, before.missiles - 1);
+    assert_eq!(game_state.entities.missiles[&missile_id].target, target_id);
+
+    let result = game_state.handle_event(events::LaunchMissile {
+        ship_id  : ship_id,
+        target_id: target_id,
+    });
+    assert!(result.is_err()); // still reloading
+}
+
+#[test]
+fn missiles_should_not_be_launched_at_close_targets() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&target_id).unwrap().position =
+        Vec2::new(10.0, 0.0);
+
+    // The ship's sensors need to pick up the target first.
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+
+    let result = game_state.handle_event(events::LaunchMissile {
+        ship_id  : ship_id,
+        target_id: target_id,
+    });
+    assert!(result.is_err());
+    assert_eq!(game_state.entities.missiles.len(), 0);
+}
+
+#[test]
+fn missiles_should_only_be_launched_at_detected_targets() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&target_id).unwrap().position =
+        Vec2::new(1e9, 0.0);
+
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+    assert!(!game_state.detections[&ship_id].contains_key(&target_id));
+
+    let undetected = game_state.handle_event(events::LaunchMissile {
+        ship_id  : ship_id,
+        target_id: target_id,
+    });
+    let nonexistent = game_state.handle_event(events::LaunchMissile {
+        ship_id  : ship_id,
+        target_id: target_id + 100,
+    });
+
+    // Both errors are the same, so they don't give away the target.
+    assert!(undetected.is_err());
+    assert_eq!(undetected, nonexistent);
+    assert_eq!(game_state.entities.missiles.len(), 0);
+}
+
+#[test]
+fn missiles_should_damage_their_target() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&target_id).unwrap().position =
+        Vec2::new(500.0, 200.0);
+
+    // The ship's sensors need to pick up the target first.
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+
+    game_state
+        .handle_event(events::LaunchMissile {
+            ship_id  : ship_id,
+            target_id: target_id,
+        })
+        .unwrap();
+
+    let mut now_s = 0.0;
+    while game_state.entities.missiles.len() > 0 {
+        assert!(now_s < 60.0, "Missile didn't detonate");
+
+        now_s += 0.5;
+        game_state.handle_event(events::Update { now_s: now_s }).unwrap();
+    }
+
+    let hull = game_state.entities.hulls[&target_id];
+    assert!(hull.integrity < hull.max_integrity);
+    assert_eq!(
+        game_state.entities.hulls[&ship_id].integrity,
+        game_state.entities.hulls[&ship_id].max_integrity,
+    );
+}
+
+#[test]
+fn defensive_launchers_should_not_launch_on_command() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state
+        .handle_event(events::SetLauncherMode {
+            ship_id: ship_id,
+            mode   : LauncherMode::Defensive,
+        })
+        .unwrap();
+
+    let result = game_state.handle_event(events::LaunchMissile {
+        ship_id  : ship_id,
+        target_id: target_id,
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn defensive_launchers_should_intercept_inbound_missiles() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&target_id).unwrap().position =
+        Vec2::new(500.0, 200.0);
+
+    game_state
+        .handle_event(events::SetLauncherMode {
+            ship_id: target_id,
+            mode   : LauncherMode::Defensive,
+        })
+        .unwrap();
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+    game_state
+        .handle_event(events::LaunchMissile {
+            ship_id  : ship_id,
+            target_id: target_id,
+        })
+        .unwrap();
+
+    let before = game_state.entities.launchers[&target_id];
+
+    let mut now_s = 0.0;
+    while game_state.entities.missiles.len() > 0 {
+        assert!(now_s < 60.0, "Missiles didn't detonate");
+
+        now_s += 0.5;
+        game_state.handle_event(events::Update { now_s: now_s }).unwrap();
+    }
+
+    let after = game_state.entities.launchers[&target_id];
+    assert!(after.missiles < before.missiles);
+    assert_eq!(
+        game_state.entities.hulls[&target_id].integrity,
+        game_state.entities.hulls[&target_id].max_integrity,
+    );
+}
+
+#[test]
+fn defensive_launchers_should_not_intercept_missiles_that_are_too_close() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&target_id).unwrap().position =
+        Vec2::new(500.0, 200.0);
+
+    game_state
+        .handle_event(events::SetLauncherMode {
+            ship_id: target_id,
+            mode   : LauncherMode::Defensive,
+        })
+        .unwrap();
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+    let missile_id = game_state
+        .handle_event(events::LaunchMissile {
+            ship_id  : ship_id,
+            target_id: target_id,
+        })
+        .unwrap();
+
+    // Move the missile right next to its target, where an interceptor's blast
+    // would hit the target too.
+    let target = game_state.entities.bodies[&target_id];
+    {
+        let missile = game_state.entities.bodies.get_mut(&missile_id).unwrap();
+        missile.position = target.position - Vec2::new(30.0, 0.0);
+        missile.velocity = target.velocity + Vec2::new(10.0, 0.0);
+    }
+
+    let before = game_state.entities.launchers[&target_id];
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+    let after = game_state.entities.launchers[&target_id];
+
+    assert_eq!(after.missiles, before.missiles);
+}
+
+#[test]
+fn ships_should_be_destroyed_when_their_hull_fails() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    let half = game_state.entities.hulls[&ship_id].max_integrity / 2.0;
+
+    game_state.damage.push((ship_id, half, DamageSource::Debris));
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    assert!(game_state.entities.ships.contains_key(&ship_id));
+
+    game_state.damage.push((ship_id, half, DamageSource::Debris));
+    game_state.handle_event(events::Update { now_s: 1.0 }).unwrap();
+
+    assert!(!game_state.entities.ships.contains_key(&ship_id));
+
+    let mut reason = None;
+    for (id, event) in game_state.notifications() {
+        if let ShipDestroyed(source) = event {
+            assert_eq!(id, ship_id);
+            reason = Some(source);
+        }
+    }
+    assert_eq!(reason, Some(DamageSource::Debris));
+}
+
+#[test]
+fn colliding_ships_should_damage_each_other() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id_a = game_state.handle_event(events::Enter::new()).unwrap();
+    let ship_id_b = game_state.handle_event(events::Enter::new()).unwrap();
+
+    {
+        let body = game_state.entities.bodies.get_mut(&ship_id_b).unwrap();
+        body.position = Vec2::new(10.0, 0.0);
+        body.velocity = Vec2::new(-1.0, 0.0);
+    }
+
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let hull_a = game_state.entities.hulls[&ship_id_a];
+    let hull_b = game_state.entities.hulls[&ship_id_b];
+    assert!(hull_a.integrity < hull_a.max_integrity);
+    assert!(hull_b.integrity < hull_b.max_integrity);
+
+    // After the impact, the ships move together and don't keep damaging each
+    // other.
+    game_state.handle_event(events::Update { now_s: 1.0 }).unwrap();
+    assert_eq!(game_state.entities.hulls[&ship_id_a], hull_a);
+}
+
+#[test]
+fn destroyed_ships_should_be_replaced_on_respawn() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    let respawn = events::Respawn {
+        ship_id: ship_id,
+        design : "scout".to_string(),
+        spawner: None,
+    };
+    assert!(respawn.validate(&game_state).is_err());
+
+    game_state.damage.push((ship_id, f64::INFINITY, DamageSource::Debris));
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let new_ship_id = game_state.handle_event(respawn).unwrap();
+
+    assert!(new_ship_id != ship_id);
+    assert_eq!(
+        game_state.entities.bodies[&new_ship_id].position,
+        game_state.spawners[0].position,
+    );
+}
+
+#[test]
+fn collisions_should_depend_on_the_size_of_the_ships() {
+    let mut game_state = GameState::new(0.0);
+
+    let courier_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let freighter_id = game_state
+        .handle_event(events::Enter {
+            design : "freighter".to_string(),
+            spawner: None,
+        })
+        .unwrap();
+
+    let reach =
+        game_state.entities.ships[&courier_id].radius
+        + game_state.entities.ships[&freighter_id].radius;
+
+    {
+        let body = game_state.entities.bodies.get_mut(&freighter_id).unwrap();
+        body.position = Vec2::new(reach - 1.0, 0.0);
+        body.velocity = Vec2::new(-1.0, 0.0);
+    }
+
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let mut notified = Vec::new();
+    for (ship_id, event) in game_state.notifications() {
+        if let Collided(other_id, damage) = event {
+            assert!(damage > 0.0);
+            notified.push((ship_id, other_id));
+        }
+    }
+
+    assert_eq!(notified.len(), 2);
+    assert!(notified.contains(&(courier_id, freighter_id)));
+    assert!(notified.contains(&(freighter_id, courier_id)));
+}
+
+#[test]
+fn ships_that_pass_each_other_should_not_collide() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id_a = game_state.handle_event(events::Enter::new()).unwrap();
+    let ship_id_b = game_state.handle_event(events::Enter::new()).unwrap();
+
+    // Same x coordinate, so the broad phase considers the ships, but they're
+    // too far apart to touch.
+    {
+        let body = game_state.entities.bodies.get_mut(&ship_id_b).unwrap();
+        body.position = Vec2::new(0.0, 100.0);
+        body.velocity = Vec2::new(1.0, -2.0);
+    }
+
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let hull_a = game_state.entities.hulls[&ship_id_a];
+    assert_eq!(hull_a.integrity, hull_a.max_integrity);
+}
+
+#[test]
+fn updates_should_update_the_game_time() {
+    let mut game_state = GameState::new(0.0);
+
+    let now_s = 10.0;
+    game_state.handle_event(events::Update { now_s: now_s }).unwrap();
+    assert_eq!(game_state.time_s, now_s);
+}
+
+#[test]
+fn destroyed_ships_should_leave_a_wreck_behind() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+    let ship    = get_body(ship_id, &mut game_state);
+    let cargo   = game_state.entities.cargo_holds[&ship_id].clone();
+
+    game_state.damage.push((ship_id, f64::INFINITY, DamageSource::Debris));
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    assert!(!game_state.entities.ships.contains_key(&ship_id));
+    assert_eq!(game_state.entities.wrecks.len(), 1);
+
+    let (&wreck_id, wreck) = game_state.entities.wrecks.iter().next().unwrap();
+    let wreck_body = game_state.entities.bodies[&wreck_id];
+
+    assert_eq!(wreck_body.velocity, ship.velocity);
+    assert_eq!(wreck_body.mass, wreck.mass());
+    assert!(wreck_body.mass < ship.mass);
+    assert!(wreck.items.iter().any(|item| item.kind == CargoKind::Scrap));
+    for item in &cargo.items {
+        assert!(wreck.items.contains(item));
+    }
+}
+
+#[test]
+fn wrecks_should_withstand_minor_impacts() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.damage.push((ship_id, f64::INFINITY, DamageSource::Debris));
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let wreck_id = *game_state.entities.wrecks.keys().next().unwrap();
+
+    game_state.damage.push((wreck_id, 1.0, DamageSource::Debris));
+    game_state.handle_event(events::Update { now_s: 1.0 }).unwrap();
+    assert!(game_state.entities.wrecks.contains_key(&wreck_id));
+
+    game_state.damage.push((wreck_id, f64::INFINITY, DamageSource::Debris));
+    game_state.handle_event(events::Update { now_s: 1.5 }).unwrap();
+    assert!(!game_state.entities.wrecks.contains_key(&wreck_id));
+}
+
+#[test]
+fn stripped_wrecks_should_decay() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id     = game_state.handle_event(events::Enter::new()).unwrap();
+    let salvager_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    // Make room for the salvage.
+    game_state
+        .handle_event(events::EjectCargo {
+            ship_id: salvager_id,
+            index  : 0,
+        })
+        .unwrap();
+
+    game_state.damage.push((ship_id, f64::INFINITY, DamageSource::Debris));
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let wreck_id = *game_state.entities.wrecks.keys().next().unwrap();
+
+    game_state
+        .handle_event(events::SalvageWreck {
+            ship_id : salvager_id,
+            wreck_id: wreck_id,
+        })
+        .unwrap();
+
+    assert!(game_state.entities.wrecks[&wreck_id].items.is_empty());
+    assert!(game_state.handle_event(events::SalvageWreck {
+        ship_id : salvager_id,
+        wreck_id: wreck_id,
+    }).is_err());
+
+    game_state.handle_event(events::Update { now_s: 30.0 }).unwrap();
+    assert!(game_state.entities.wrecks.contains_key(&wreck_id));
+
+    game_state.handle_event(events::Update { now_s: 61.0 }).unwrap();
+    assert!(!game_state.entities.wrecks.contains_key(&wreck_id));
+}
+
+#[test]
+fn drones_should_harvest_wrecks_over_time() {
+    let mut game_state = GameState::new(0.0);
+
+    let harvester_id = game_state
+        .handle_event(events::Enter {
+            design : "prospector".to_string(),
+            spawner: None,
+        })
+        .unwrap();
+    let ship_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.damage.push((ship_id, f64::INFINITY, DamageSource::Debris));
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let wreck_id   = *game_state.entities.wrecks.keys().next().unwrap();
+    let wreck_mass = game_state.entities.wrecks[&wreck_id].mass();
+    let cargo_mass = game_state.entities.cargo_holds[&harvester_id].mass();
+
+    game_state
+        .handle_event(events::DispatchDrones {
+            ship_id : harvester_id,
+            wreck_id: wreck_id,
+        })
+        .unwrap();
+    game_state.handle_event(events::Update { now_s: 1.0 }).unwrap();
+    game_state.handle_event(events::Update { now_s: 1.5 }).unwrap();
+
+    let salvaged = game_state.entities.drone_bays[&harvester_id].salvaged;
+    assert!(salvaged > 0.0);
+    assert!(salvaged < wreck_mass);
+    assert!(
+        (game_state.entities.cargo_holds[&harvester_id].mass()
+            - (cargo_mass + salvaged)).abs() < 1e-9
+    );
+    assert!(
+        (game_state.entities.wrecks[&wreck_id].mass()
+            - (wreck_mass - salvaged)).abs() < 1e-9
+    );
+    assert_eq!(
+        get_body(wreck_id, &mut game_state).mass,
+        game_state.entities.wrecks[&wreck_id].mass(),
+    );
+
+    game_state
+        .handle_event(events::RecallDrones { ship_id: harvester_id })
+        .unwrap();
+    game_state.handle_event(events::Update { now_s: 2.0 }).unwrap();
+
+    assert_eq!(
+        game_state.entities.drone_bays[&harvester_id].salvaged,
+        salvaged,
+    );
+}
+
+#[test]
+fn ships_without_drone_bay_should_not_dispatch_drones() {
+    let mut game_state = GameState::new(0.0);
+
+    let ship_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let victim_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.damage.push((victim_id, f64::INFINITY, DamageSource::Debris));
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let wreck_id = *game_state.entities.wrecks.keys().next().unwrap();
+
+    let result = game_state.handle_event(events::DispatchDrones {
+        ship_id : ship_id,
+        wreck_id: wreck_id,
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn thrusting_ships_should_be_easier_to_detect() {
+    let mut game_state = GameState::new(0.0);
+
+    let observer_id = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id   = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&target_id).unwrap().position =
+        Vec2::new(15000.0, 0.0);
+
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+    assert!(!game_state.detections[&observer_id].contains_key(&target_id));
+
+    game_state
+        .handle_event(events::ScheduleManeuver {
+            ship_id: target_id,
+            data   : ManeuverData {
+                start_s    : 0.5,
+                duration_s : 10.0,
+                angle      : 0.0,
+                thrust     : 1.0,
+                orientation: Orientation::Fixed,
+            },
+        })
+        .unwrap();
+    game_state.handle_event(events::Update { now_s: 1.0 }).unwrap();
+    assert!(game_state.detections[&observer_id].contains_key(&target_id));
+}
+
+#[test]
+fn active_radar_should_reveal_the_emitter() {
+    let mut game_state = GameState::new(0.0);
+
+    let emitter_id  = game_state.handle_event(events::Enter::new()).unwrap();
+    let observer_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&observer_id).unwrap().position =
+        Vec2::new(15000.0, 0.0);
+
+    game_state
+        .handle_event(events::SetRadar {
+            ship_id: emitter_id,
+            active : true,
+        })
+        .unwrap();
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let detection = game_state.detections[&observer_id][&emitter_id];
+    assert_eq!(detection.quality, DetectionQuality::Identified);
+
+    // The radar picks up the observer too, but not well enough to identify
+    // it.
+    let detection = game_state.detections[&emitter_id][&observer_id];
+    assert_eq!(detection.quality, DetectionQuality::Unknown);
+}
+
+#[test]
+fn focused_sensors_should_see_further_inside_the_cone_only() {
+    let mut game_state = GameState::new(0.0);
+
+    let observer_id = game_state.handle_event(events::Enter::new()).unwrap();
+    let ahead_id    = game_state.handle_event(events::Enter::new()).unwrap();
+    let behind_id   = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&ahead_id).unwrap().position =
+        Vec2::new(15000.0, 0.0);
+    game_state.entities.bodies.get_mut(&behind_id).unwrap().position =
+        Vec2::new(-8000.0, 0.0);
+
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+    assert!(!game_state.detections[&observer_id].contains_key(&ahead_id));
+    assert!(game_state.detections[&observer_id].contains_key(&behind_id));
+
+    game_state
+        .handle_event(events::SetSensorFocus {
+            ship_id: observer_id,
+            focus  : Some(SensorFocus {
+                angle: 0.0,
+                width: PI / 2.0,
+            }),
+        })
+        .unwrap();
+    game_state.handle_event(events::Update { now_s: 1.0 }).unwrap();
+    assert!(game_state.detections[&observer_id].contains_key(&ahead_id));
+    assert!(!game_state.detections[&observer_id].contains_key(&behind_id));
+}
+
+#[test]
+fn unidentified_entities_should_only_be_sent_as_contacts() {
+    let mut game_state = GameState::new(0.0);
+
+    let observer_id = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id   = game_state.handle_event(events::Enter::new()).unwrap();
+    let far_away_id = game_state.handle_event(events::Enter::new()).unwrap();
+
+    let target_position = Vec2::new(8000.0, 0.0);
+    game_state.entities.bodies.get_mut(&target_id).unwrap().position =
+        target_position;
+    game_state.entities.bodies.get_mut(&far_away_id).unwrap().position =
+        Vec2::new(100000.0, 0.0);
+
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let entities: Vec<_> = game_state.export_entities().collect();
+    for entity in &entities {
+        let update = game_state.update_for(observer_id, entity);
+
+        if entity.id == target_id {
+            match update {
+                Some(UpdateContact(id, contact)) => {
+                    assert_eq!(id, target_id);
+                    assert_eq!(contact.classification, Classification::Unknown);
+                    assert!(
+                        (contact.position - target_position).norm()
+                            <= contact.uncertainty
+                    );
+                },
+                update =>
+                    panic!("Unexpected update: {:?}", update),
+            }
+        }
+        if entity.id == far_away_id {
+            assert!(update.is_none());
+        }
+        if entity.id == observer_id {
+            assert_eq!(update, Some(UpdateEntity(entity.clone())));
+        }
+    }
+}
+
+#[test]
+fn identified_ships_should_not_reveal_their_equipment() {
+    let mut game_state = GameState::new(0.0);
+
+    let observer_id = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id   = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let entities: Vec<_> = game_state.export_entities().collect();
+    for entity in &entities {
+        if entity.id != target_id {
+            continue;
+        }
+
+        match game_state.update_for(observer_id, entity) {
+            Some(UpdateEntity(entity)) => {
+                assert!(entity.ship.is_some());
+                assert!(entity.cargo.is_none());
+                assert!(entity.drone_bay.is_none());
+                assert!(entity.launcher.is_none());
+                assert!(entity.sensors.is_none());
+            },
+            update =>
+                panic!("Unexpected update: {:?}", update),
+        }
+    }
+}
+
+#[test]
+fn unknown_contacts_should_be_classified_when_their_engines_fire() {
+    let mut game_state = GameState::new(0.0);
+
+    let observer_id = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id   = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&target_id).unwrap().position =
+        Vec2::new(30000.0, 0.0);
+
+    game_state
+        .handle_event(events::ScheduleManeuver {
+            ship_id: target_id,
+            data   : ManeuverData {
+                start_s    : 0.5,
+                duration_s : 10.0,
+                angle      : 0.0,
+                thrust     : 1.0,
+                orientation: Orientation::Fixed,
+            },
+        })
+        .unwrap();
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let entities: Vec<_> = game_state.export_entities().collect();
+    for entity in &entities {
+        if entity.id != target_id {
+            continue;
+        }
+
+        match game_state.update_for(observer_id, entity) {
+            Some(UpdateContact(_, contact)) =>
+                assert_eq!(contact.classification, Classification::Ship),
+            update =>
+                panic!("Unexpected update: {:?}", update),
+        }
+    }
+}
+
+#[test]
+fn maneuvers_without_thrust_should_not_reveal_a_contact() {
+    let mut game_state = GameState::new(0.0);
+
+    let observer_id = game_state.handle_event(events::Enter::new()).unwrap();
+    let target_id   = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&target_id).unwrap().position =
+        Vec2::new(10000.0, 0.0);
+
+    game_state
+        .handle_event(events::ScheduleManeuver {
+            ship_id: target_id,
+            data   : ManeuverData {
+                start_s    : 0.5,
+                duration_s : 10.0,
+                angle      : 0.0,
+                thrust     : 0.0,
+                orientation: Orientation::Fixed,
+            },
+        })
+        .unwrap();
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    let detection = game_state.detections[&observer_id][&target_id];
+    assert_eq!(detection.quality, DetectionQuality::Unknown);
+}
+
+#[test]
+fn ships_should_be_derived_from_their_design() {
+    let mut game_state = GameState::new(0.0);
+
+    let design = ShipDesign::new("test")
+        .with_module(ModuleKind::Armor     , 0.5, 50.0)
+        .with_module(ModuleKind::Cargo     , 0.5, 2.0)
+        .with_module(ModuleKind::Propulsion, 0.5, 3.0)
+        .with_module(ModuleKind::Propulsion, 0.5, 3.0)
+        .with_module(ModuleKind::Sensors   , 0.5, 1000.0);
+    game_state.designs.push(design.clone());
+
+    let ship_id = game_state
+        .handle_event(events::Enter {
+            design : "test".to_string(),
+            spawner: None,
+        })
+        .unwrap();
+
+    let ship  = game_state.entities.ships[&ship_id];
+    let cargo = &game_state.entities.cargo_holds[&ship_id];
+
+    assert_eq!(ship.thrust, 6.0);
+    assert_eq!(game_state.entities.sensors[&ship_id].range, 1000.0);
+    assert_eq!(cargo.capacity, 2.0);
+    assert_eq!(
+        game_state.entities.hulls[&ship_id].max_integrity,
+        design.integrity(),
+    );
+    assert_eq!(
+        game_state.entities.bodies[&ship_id].mass,
+        design.mass() + cargo.mass(),
+    );
+
+    // The design has no weapons and no FTL drive.
+    assert!(!game_state.entities.launchers.contains_key(&ship_id));
+    assert!(!game_state.entities.ftl_drives.contains_key(&ship_id));
+}
+
+#[test]
+fn ships_of_unknown_designs_should_not_enter() {
+    let mut game_state = GameState::new(0.0);
+
+    let result = game_state.handle_event(events::Enter {
+        design : "no such design".to_string(),
+        spawner: None,
+    });
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn ships_should_enter_at_the_chosen_spawner() {
+    let mut game_state = GameState::new(0.0);
+
+    let celestial = Celestial {
+        position: Vec2::new(0.0, 0.0),
+        size    : 100.0,
+    };
+
+    InitialState::new()
+        .with_celestial(celestial)
+        .with_spawner(Spawner {
+            name    : "outpost".to_string(),
+            position: Vec2::new(5000.0, 0.0),
+            velocity: Vec2::new(0.0, 0.0),
+            jitter  : 0.0,
+        })
+        .with_orbit_spawner(OrbitSpawner {
+            name     : "orbit".to_string(),
+            celestial: 0,
+            altitude : 100.0,
+            jitter   : 0.0,
+        })
+        .apply(&mut game_state);
+
+    let enter = |game_state: &mut GameState, spawner: Option<&str>| {
+        game_state.handle_event(events::Enter {
+            design : "scout".to_string(),
+            spawner: spawner.map(|name| name.to_string()),
+        })
+    };
+
+    let default_id = enter(&mut game_state, None).unwrap();
+    let outpost_id = enter(&mut game_state, Some("outpost")).unwrap();
+    let orbit_id   = enter(&mut game_state, Some("orbit")).unwrap();
+
+    assert!(enter(&mut game_state, Some("no such spawner")).is_err());
+
+    assert_eq!(
+        game_state.entities.bodies[&default_id].position,
+        Spawner::new().position,
+    );
+    assert_eq!(
+        game_state.entities.bodies[&outpost_id].position,
+        Vec2::new(5000.0, 0.0),
+    );
+
+    // The ship should be in a circular orbit at the requested altitude.
+    let body     = game_state.entities.bodies[&orbit_id];
+    let radius   = body.position - celestial.position;
+    let distance = radius.norm();
+    let speed    = body.velocity.norm();
+    let circular =
+        (GRAVITATIONAL_CONSTANT * celestial.mass() / distance).sqrt();
+
+    assert!((distance - 200.0).abs() < 1e-6);
+    assert!((speed - circular).abs() < 1e-6);
+    assert!(body.velocity.dot(&radius).abs() < 1e-6);
+}
+
+#[test]
+fn spawners_should_spread_out_ships() {
+    let mut game_state = GameState::new(0.0);
+
+    let spawner = Spawner {
+        jitter: 100.0,
+        .. Spawner::new()
+    };
+
+    InitialState::new()
+        .with_spawner(spawner.clone())
+        .apply(&mut game_state);
+
+    let ship_a = game_state.handle_event(events::Enter::new()).unwrap();
+    let ship_b = game_state.handle_event(events::Enter::new()).unwrap();
+
+    let position_a = game_state.entities.bodies[&ship_a].position;
+    let position_b = game_state.entities.bodies[&ship_b].position;
+
+    assert!(position_a != position_b);
+    assert!((position_a - spawner.position).norm() <= spawner.jitter);
+    assert!((position_b - spawner.position).norm() <= spawner.jitter);
+}
+
+#[test]
+fn ships_should_not_enter_inside_of_celestials() {
+    let mut game_state = GameState::new(0.0);
+
+    InitialState::random().apply(&mut game_state);
+
+    let spawners: Vec<_> = game_state.spawners
+        .iter()
+        .map(|spawner| spawner.name.clone())
+        .collect();
+
+    for spawner in spawners {
+        for _ in 0 .. 20 {
+            let ship_id = game_state
+                .handle_event(events::Enter {
+                    design : "freighter".to_string(),
+                    spawner: Some(spawner.clone()),
+                })
+                .unwrap();
+
+            let body   = game_state.entities.bodies[&ship_id];
+            let radius = game_state.entities.ships[&ship_id].radius;
+
+            for (_, planet) in &game_state.entities.planets {
+                let distance = (body.position - planet.position).norm();
+                assert!(distance > planet.radius + radius);
+            }
+        }
+    }
+}
+
+
+#[test]
+fn probes_should_patrol_the_inner_system() {
+    let mut game_state = GameState::new(0.0);
+
+    InitialState::new()
+        .with_celestial(Celestial {
+            position: Vec2::new(0.0, 0.0),
+            size    : 100.0,
+        })
+        .with_celestial(Celestial {
+            position: Vec2::new(5000.0, 0.0),
+            size    : 50.0,
+        })
+        .with_celestial(Celestial {
+            position: Vec2::new(100000.0, 0.0),
+            size    : 50.0,
+        })
+        .with_swarm(Swarm {
+            design       : ShipDesign::probe(),
+            size         : 2,
+            patrol_range : 10000.0,
+            pursuit_range: 1000.0,
+        })
+        .apply(&mut game_state);
+
+    assert_eq!(game_state.entities.probes.len(), 2);
+    for (id, probe) in &game_state.entities.probes {
+        assert_eq!(probe.patrol.len(), 2);
+        assert_eq!(
+            game_state.entities.bodies[id].position,
+            probe.patrol[probe.waypoint],
+        );
+    }
+}
+
+#[test]
+fn players_should_not_enter_as_probes() {
+    let mut game_state = GameState::new(0.0);
+
+    InitialState::new()
+        .with_swarm(Swarm {
+            design       : ShipDesign::probe(),
+            size         : 1,
+            patrol_range : 0.0,
+            pursuit_range: 5000.0,
+        })
+        .apply(&mut game_state);
+
+    let result = game_state.handle_event(events::Enter {
+        design : ShipDesign::probe().name,
+        spawner: None,
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn probes_should_pursue_detected_ships() {
+    let mut game_state = GameState::new(0.0);
+
+    InitialState::new()
+        .with_swarm(Swarm {
+            design       : ShipDesign::probe(),
+            size         : 1,
+            patrol_range : 0.0,
+            pursuit_range: 5000.0,
+        })
+        .apply(&mut game_state);
+
+    let probe_id = *game_state.entities.probes.keys().next().unwrap();
+    let ship_id  = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.entities.bodies.get_mut(&ship_id).unwrap().position =
+        Vec2::new(2000.0, 0.0);
+
+    // The probe makes its first decision before it has detected anything.
+    // Once its sensors have picked up the ship, it goes after it.
+    for i in 1 .. 13 {
+        game_state.handle_event(events::Update { now_s: i as f64 * 0.5 })
+            .unwrap();
+    }
+
+    assert_eq!(
+        game_state.entities.probes[&probe_id].target,
+        Some(ship_id),
+    );
+
+    let pursuit = game_state.entities.maneuvers
+        .values()
+        .find(|maneuver| maneuver.ship_id == probe_id)
+        .unwrap();
+    assert!(pursuit.data.angle.cos() > 0.9);
+}
+
+#[test]
+fn docked_ships_should_move_with_their_station() {
+    let mut game_state = GameState::new(0.0);
+
+    InitialState::new()
+        .with_station(StationSite {
+            name    : "test".to_string(),
+            position: Vec2::new(0.0, 0.0),
+            velocity: Vec2::new(1.0, 0.0),
+        })
+        .apply(&mut game_state);
+
+    let station_id = *game_state.entities.stations.keys().next().unwrap();
+    let ship_id    = game_state.handle_event(events::Enter::new()).unwrap();
+
+    // The ship's sensors need to pick up the station first.
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+
+    game_state
+        .handle_event(events::Dock {
+            ship_id   : ship_id,
+            station_id: station_id,
+        })
+        .unwrap();
+    game_state.handle_event(events::Update { now_s: 0.5 }).unwrap();
+
+    assert_eq!(
+        get_body(ship_id, &mut game_state).position,
+        get_body(station_id, &mut game_state).position,
+    );
+    assert!(game_state.handle_event(events::ScheduleManeuver {
+        ship_id: ship_id,
+        data   : ManeuverData {
+            start_s    : 0.5,
+            duration_s : 1.0,
+            angle      : 0.0,
+            thrust     : 1.0,
+            orientation: Orientation::Fixed,
+        },
+    }).is_err());
+
+    game_state.entities.hulls.get_mut(&ship_id).unwrap().integrity = 1.0;
+    game_state.entities.ftl_drives.get_mut(&ship_id).unwrap().fuel_s = 0.0;
+
+    game_state.handle_event(events::Repair { ship_id: ship_id }).unwrap();
+    game_state.handle_event(events::Refuel { ship_id: ship_id }).unwrap();
+
+    let hull = game_state.entities.hulls[&ship_id];
+    assert_eq!(hull.integrity, hull.max_integrity);
+    let drive = game_state.entities.ftl_drives[&ship_id];
+    assert_eq!(drive.fuel_s, drive.max_fuel_s);
+
+    game_state.handle_event(events::Undock { ship_id: ship_id }).unwrap();
+    assert!(!game_state.entities.dockings.contains_key(&ship_id));
+    assert!(game_state.handle_event(events::Repair { ship_id: ship_id })
+        .is_err());
+}
+
+#[test]
+fn ships_should_only_dock_after_matching_velocity() {
+    let mut game_state = GameState::new(0.0);
+
+    InitialState::new()
+        .with_station(StationSite {
+            name    : "test".to_string(),
+            position: Vec2::new(0.0, 0.0),
+            velocity: Vec2::new(1.0, 0.0),
+        })
+        .apply(&mut game_state);
+
+    let station_id = *game_state.entities.stations.keys().next().unwrap();
+    let ship_id    = game_state.handle_event(events::Enter::new()).unwrap();
+
+    game_state.handle_event(events::Update { now_s: 0.0 }).unwrap();
+    game_state.entities.bodies.get_mut(&ship_id).unwrap().velocity =
+        Vec2::new(10.0, 0.0);
+
+    let result = game_state.handle_event(events::Dock {
+        ship_id   : ship_id,
+        station_id: station_id,
+    });
+    assert!(result.is_err());
+    assert!(!game_state.entities.dockings.contains_key(&ship_id));
+}
+
+fn get_body(body_id: EntityId, game_state: &mut GameState) -> Body {
+    for entity in game_state.export_entities() {
+        if entity.id == body_id {
+            return entity.body.unwrap();
+        }
+    }
+
+    unreachable!();
+}
+
+fn schedule_maneuver(ship_id: EntityId, start_s: f64, duration_s: f64)
+    -> events::ScheduleManeuver
+{
+    events::ScheduleManeuver {
+        ship_id: ship_id,
+
+        data: ManeuverData {
+            start_s    : start_s,
+            duration_s : duration_s,
+            angle      : 0.0,
+            thrust     : 1.0,
+            orientation: Orientation::Fixed,
+        }
+    }
 }
 
 fn get_maneuver_id(ship_id: EntityId, game_state: &mut GameState) -> EntityId {
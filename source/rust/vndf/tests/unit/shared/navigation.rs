@@ -0,0 +1,520 @@
+use std::collections::BTreeMap;
+use std::f64;
+use std::f64::consts::PI;
+
+use nalgebra::{
+    Norm,
+    Vec2,
+};
+
+use vndf::shared::color::Colors;
+use vndf::shared::game::data::{
+    Body,
+    ManeuverData,
+    Planet,
+};
+use vndf::shared::game::logic::{
+    GRAVITATIONAL_CONSTANT,
+    apply_gravity,
+    integrate,
+};
+use vndf::shared::game::navigation::{
+    ARRIVAL_TOLERANCE,
+    MAX_PREDICTION_STEPS,
+    PREDICTION_STEP_S,
+    Apsis,
+    Burn,
+    Orbit,
+    Target,
+    closest_approach,
+    dominant_planet,
+    plan_hohmann,
+    plan_rendezvous,
+    predict,
+};
+use vndf::shared::util::angle_of;
+
+
+#[test]
+fn rendezvous_plans_should_account_for_gravity() {
+    let planets = [
+        Planet {
+            position: Vec2::new(0.0, 0.0),
+            radius  : 100.0,
+            mass    : 1e15,
+            color   : Colors::red(),
+        },
+    ];
+
+    let ship   = body(Vec2::new(2000.0, 0.0), Vec2::new(0.0, 0.0));
+    let target = body(Vec2::new(0.0, 2000.0), Vec2::new(0.0, 0.0));
+
+    let now_s       = 10.0;
+    let departure_s = 20.0;
+    let arrival_s   = 220.0;
+
+    let burns = plan_rendezvous(
+        &ship,
+        &target,
+        &planets,
+        now_s,
+        departure_s,
+        arrival_s,
+    ).unwrap();
+
+    assert_eq!(burns.len(), 2);
+    assert_eq!(burns[0].time_s, departure_s);
+    assert_eq!(burns[1].time_s, arrival_s);
+
+    // Gravity makes a difference, so a straight course would miss.
+    let straight_course = (target.position - ship.position) / 200.0;
+    assert!((burns[0].delta_v - straight_course).norm() > 0.1);
+
+    // Follow the plan.
+    let mut ship = predict(&ship, &planets, departure_s - now_s).unwrap();
+    ship.velocity = ship.velocity + burns[0].delta_v;
+    let mut ship = predict(&ship, &planets, arrival_s - departure_s).unwrap();
+    ship.velocity = ship.velocity + burns[1].delta_v;
+
+    let target = predict(&target, &planets, arrival_s - now_s).unwrap();
+
+    assert!((ship.position - target.position).norm() <= ARRIVAL_TOLERANCE);
+    assert!((ship.velocity - target.velocity).norm() < 1e-9);
+}
+
+#[test]
+fn rendezvous_plans_should_not_go_through_planets() {
+    let planets = [
+        Planet {
+            position: Vec2::new(0.0, 0.0),
+            radius  : 100.0,
+            mass    : 1.0,
+            color   : Colors::red(),
+        },
+    ];
+
+    let ship   = body(Vec2::new(-1000.0, 0.0), Vec2::new(0.0, 0.0));
+    let target = body(Vec2::new( 1000.0, 0.0), Vec2::new(0.0, 0.0));
+
+    let burns = plan_rendezvous(&ship, &target, &planets, 0.0, 0.0, 100.0);
+
+    assert!(burns.is_none());
+}
+
+#[test]
+fn rendezvous_plans_should_reject_invalid_time_spans() {
+    let ship   = body(Vec2::new(0.0, 0.0), Vec2::new(0.0, 0.0));
+    let target = body(Vec2::new(100.0, 0.0), Vec2::new(0.0, 0.0));
+
+    let too_long = MAX_PREDICTION_STEPS as f64 * PREDICTION_STEP_S * 2.0;
+
+    assert!(plan_rendezvous(&ship, &target, &[], 0.0, 0.0, too_long).is_none());
+    assert!(
+        plan_rendezvous(&ship, &target, &[], 0.0, f64::NAN, 10.0).is_none()
+    );
+    assert!(plan_rendezvous(&ship, &target, &[], 0.0, 0.0, 10.0).is_some());
+}
+
+#[test]
+fn burns_should_be_converted_to_full_thrust_maneuvers() {
+    let burn = Burn {
+        time_s : 5.0,
+        delta_v: Vec2::new(0.0, 2.0),
+    };
+
+    let maneuver = burn.to_maneuver(10.0, 4.0).unwrap();
+
+    // The maneuver is centered on the burn.
+    assert_eq!(maneuver.start_s, 2.5);
+    assert_eq!(maneuver.duration_s, 5.0);
+    assert_eq!(maneuver.thrust, 1.0);
+    assert!((maneuver.angle - PI / 2.0).abs() < 1e-9);
+
+    let no_burn = Burn {
+        time_s : 5.0,
+        delta_v: Vec2::new(0.0, 0.0),
+    };
+    assert!(no_burn.to_maneuver(10.0, 4.0).is_none());
+}
+
+#[test]
+fn plans_should_hold_up_with_burns_that_take_time() {
+    let planets = [
+        Planet {
+            position: Vec2::new(0.0, 0.0),
+            radius  : 100.0,
+            mass    : 1e15,
+            color   : Colors::red(),
+        },
+    ];
+
+    let ship   = body(Vec2::new(2000.0, 0.0), Vec2::new(0.0, 0.0));
+    let target = body(Vec2::new(0.0, 2000.0), Vec2::new(0.0, 0.0));
+
+    let now_s      = 10.0;
+    let max_thrust = 1.0;
+
+    let burns =
+        plan_rendezvous(&ship, &target, &planets, now_s, 20.0, 220.0).unwrap();
+    let maneuvers: Vec<_> = burns
+        .iter()
+        .filter_map(|burn| burn.to_maneuver(ship.mass, max_thrust))
+        .collect();
+
+    let end_s  = maneuvers[1].end_s();
+    let ship   = execute(&ship, &planets, &maneuvers, max_thrust, now_s, end_s);
+    let target = predict(&target, &planets, end_s - now_s).unwrap();
+
+    // The burns take several seconds each, so the ship can't arrive exactly.
+    assert!((ship.position - target.position).norm() < 5.0);
+    assert!((ship.velocity - target.velocity).norm() < 0.1);
+}
+
+#[test]
+fn hohmann_transfers_should_reach_the_target_orbit() {
+    let planet = Planet {
+        position: Vec2::new(0.0, 0.0),
+        radius  : 100.0,
+        mass    : 1e15,
+        color   : Colors::red(),
+    };
+
+    let ship = orbiting(&planet, 1000.0);
+
+    let burns = plan_hohmann(&ship, &planet, 2000.0, None, 0.0, 10.0)
+        .unwrap();
+
+    assert_eq!(burns.len(), 2);
+    assert_eq!(burns[0].time_s, 10.0);
+
+    let ship = follow(&ship, &planet, &burns, 0.0);
+
+    let distance = (ship.position - planet.position).norm();
+    assert!((distance - 2000.0).abs() < 20.0);
+    assert!(
+        (ship.velocity.norm() - circular_speed(&planet, 2000.0)).abs()
+            < 0.01 * circular_speed(&planet, 2000.0)
+    );
+}
+
+#[test]
+fn hohmann_transfers_should_arrive_at_the_target_angle() {
+    let planet = Planet {
+        position: Vec2::new(0.0, 0.0),
+        radius  : 100.0,
+        mass    : 1e15,
+        color   : Colors::red(),
+    };
+
+    let ship = orbiting(&planet, 1000.0);
+
+    let target_angle = PI / 2.0;
+    let burns =
+        plan_hohmann(&ship, &planet, 2000.0, Some(target_angle), 0.0, 0.0)
+            .unwrap();
+
+    // The ship has to wait until it's on the opposite side.
+    assert!(burns[0].time_s > 0.0);
+
+    let ship = follow(&ship, &planet, &burns, 0.0);
+
+    let angle = angle_of(ship.position - planet.position);
+    assert!((angle - target_angle).abs() < 0.05);
+}
+
+#[test]
+fn hohmann_transfers_should_require_a_circular_orbit() {
+    let planet = Planet {
+        position: Vec2::new(0.0, 0.0),
+        radius  : 100.0,
+        mass    : 1e15,
+        color   : Colors::red(),
+    };
+
+    let speed = circular_speed(&planet, 1000.0);
+
+    let eccentric = body(Vec2::new(1000.0, 0.0), Vec2::new(0.0, speed * 1.3));
+    let escaping  = body(Vec2::new(1000.0, 0.0), Vec2::new(0.0, speed * 2.0));
+
+    assert!(
+        plan_hohmann(&eccentric, &planet, 2000.0, None, 0.0, 0.0).is_none()
+    );
+    assert!(
+        plan_hohmann(&escaping, &planet, 2000.0, None, 0.0, 0.0).is_none()
+    );
+}
+
+#[test]
+fn closest_approach_should_be_found_between_steps() {
+    let ship   = body(Vec2::new(   0.0,   0.0), Vec2::new( 10.0, 0.0));
+    let target = body(Vec2::new(1000.0, 100.0), Vec2::new(-10.0, 0.0));
+
+    // The step doesn't line up with the time of closest approach.
+    let approach =
+        closest_approach(&ship, Target::Body(target), &[], 10.0, 200.0, 7.0)
+            .unwrap();
+
+    assert!((approach.time_s - 60.0).abs() < 1e-6);
+    assert!((approach.distance - 100.0).abs() < 1e-6);
+    assert!((approach.position - Vec2::new(500.0, 0.0)).norm() < 1e-6);
+}
+
+#[test]
+fn closest_approach_should_work_for_fixed_targets() {
+    let planets = [
+        Planet {
+            position: Vec2::new(0.0, 500.0),
+            radius  : 100.0,
+            mass    : 1.0,
+            color   : Colors::red(),
+        },
+    ];
+
+    let ship = body(Vec2::new(-1000.0, 0.0), Vec2::new(10.0, 0.0));
+
+    let approach = closest_approach(
+        &ship,
+        Target::Fixed(planets[0].position),
+        &planets,
+        0.0,
+        1000.0,
+        1.0,
+    ).unwrap();
+
+    assert!((approach.time_s - 100.0).abs() < 0.1);
+    assert!((approach.distance - 500.0).abs() < 0.1);
+}
+
+#[test]
+fn closest_approach_should_end_at_small_planets_between_steps() {
+    // The ship passes the planet within a single step.
+    let planets = [
+        Planet {
+            position: Vec2::new(125.0, 0.0),
+            radius  : 10.0,
+            mass    : 1.0,
+            color   : Colors::red(),
+        },
+    ];
+
+    let ship   = body(Vec2::new(0.0, 0.0), Vec2::new(100.0, 0.0));
+    let target = Target::Fixed(Vec2::new(1000.0, 0.0));
+
+    let approach =
+        closest_approach(&ship, target, &planets, 0.0, 20.0, 0.5).unwrap();
+
+    assert!(approach.position.x <= 115.0 + 1e-6);
+    assert!(approach.distance >= 885.0 - 1e-6);
+
+    assert!(predict(&ship, &planets, 2.0).is_none());
+}
+
+#[test]
+fn closest_approach_should_limit_the_number_of_steps() {
+    let ship   = body(Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0));
+    let target = Target::Fixed(Vec2::new(100.0, 0.0));
+
+    let too_long = MAX_PREDICTION_STEPS as f64 * 2.0;
+
+    assert!(closest_approach(&ship, target, &[], 0.0, too_long, 1.0).is_none());
+    assert!(closest_approach(&ship, target, &[], 0.0, f64::NAN, 1.0).is_none());
+}
+
+#[test]
+fn orbits_should_describe_circular_orbits() {
+    let planet = Planet {
+        position: Vec2::new(0.0, 0.0),
+        radius  : 100.0,
+        mass    : 1e15,
+        color   : Colors::red(),
+    };
+
+    let orbit = Orbit::of(&orbiting(&planet, 1000.0), &planet).unwrap();
+
+    let mu     = GRAVITATIONAL_CONSTANT * planet.mass;
+    let period = 2.0 * PI * (1000.0 * 1000.0 * 1000.0 / mu).sqrt();
+
+    assert!(orbit.eccentricity < 1e-9);
+    assert!((orbit.periapsis - 1000.0).abs() < 1e-6);
+    assert!((orbit.apoapsis.unwrap() - 1000.0).abs() < 1e-6);
+    assert!((orbit.period_s.unwrap() - period).abs() < 1e-6);
+    assert!(!orbit.intersects_surface);
+}
+
+#[test]
+fn orbits_should_predict_the_next_apsis() {
+    let planet = Planet {
+        position: Vec2::new(0.0, 0.0),
+        radius  : 100.0,
+        mass    : 1e15,
+        color   : Colors::red(),
+    };
+    let planets = [planet];
+
+    // Slightly faster than a circular orbit, and falling towards the planet
+    let speed = circular_speed(&planet, 1000.0) * 1.2;
+    let ship  = body(Vec2::new(1000.0, 0.0), Vec2::new(-3.0, speed));
+
+    let orbit = Orbit::of(&ship, &planet).unwrap();
+    assert!(orbit.eccentricity > 0.1 && orbit.eccentricity < 1.0);
+    assert!(orbit.periapsis < 1000.0);
+    assert!(orbit.apoapsis.unwrap() > 1000.0);
+    assert!(!orbit.intersects_surface);
+
+    let (apsis, time_s) = orbit.next_apsis.unwrap();
+    assert_eq!(apsis, Apsis::Periapsis);
+
+    let ship     = predict(&ship, &planets, time_s).unwrap();
+    let distance = (ship.position - planet.position).norm();
+    assert!((distance - orbit.periapsis).abs() < 0.01 * orbit.periapsis);
+}
+
+#[test]
+fn orbits_should_handle_escape_trajectories() {
+    let planet = Planet {
+        position: Vec2::new(0.0, 0.0),
+        radius  : 100.0,
+        mass    : 1e15,
+        color   : Colors::red(),
+    };
+    let planets = [planet];
+
+    let approaching = body(Vec2::new(1000.0, 0.0), Vec2::new(-20.0, 5.0));
+    let orbit       = Orbit::of(&approaching, &planet).unwrap();
+
+    assert!(orbit.eccentricity > 1.0);
+    assert_eq!(orbit.apoapsis, None);
+    assert_eq!(orbit.period_s, None);
+    assert!(!orbit.intersects_surface);
+
+    let (apsis, time_s) = orbit.next_apsis.unwrap();
+    assert_eq!(apsis, Apsis::Periapsis);
+
+    let ship     = predict(&approaching, &planets, time_s).unwrap();
+    let distance = (ship.position - planet.position).norm();
+    assert!((distance - orbit.periapsis).abs() < 0.01 * orbit.periapsis);
+
+    let leaving = body(Vec2::new(1000.0, 0.0), Vec2::new(20.0, 5.0));
+    let orbit   = Orbit::of(&leaving, &planet).unwrap();
+
+    assert_eq!(orbit.next_apsis, None);
+}
+
+#[test]
+fn orbits_should_detect_impacts() {
+    let planet = Planet {
+        position: Vec2::new(0.0, 0.0),
+        radius  : 100.0,
+        mass    : 1e15,
+        color   : Colors::red(),
+    };
+
+    let speed = circular_speed(&planet, 1000.0) * 0.1;
+    let ship  = body(Vec2::new(1000.0, 0.0), Vec2::new(0.0, speed));
+
+    let orbit = Orbit::of(&ship, &planet).unwrap();
+
+    assert!(orbit.periapsis < planet.radius);
+    assert!(orbit.intersects_surface);
+}
+
+#[test]
+fn the_dominant_planet_should_pull_the_hardest() {
+    let mut planets = BTreeMap::new();
+    planets.insert(0, Planet {
+        position: Vec2::new(0.0, 0.0),
+        radius  : 100.0,
+        mass    : 1e15,
+        color   : Colors::red(),
+    });
+    planets.insert(1, Planet {
+        position: Vec2::new(5000.0, 0.0),
+        radius  : 10.0,
+        mass    : 1e12,
+        color   : Colors::blue(),
+    });
+
+    let near_moon   = dominant_planet(Vec2::new(4900.0, 0.0), &planets);
+    let near_planet = dominant_planet(Vec2::new(4000.0, 0.0), &planets);
+
+    assert_eq!(near_moon.unwrap().0, 1);
+    assert_eq!(near_planet.unwrap().0, 0);
+    assert!(dominant_planet(Vec2::new(0.0, 0.0), &BTreeMap::new()).is_none());
+}
+
+
+fn body(position: Vec2<f64>, velocity: Vec2<f64>) -> Body {
+    Body {
+        position: position,
+        velocity: velocity,
+        force   : Vec2::new(0.0, 0.0),
+        mass    : 1.0,
+    }
+}
+
+/// Returns a body in a counterclockwise circular orbit, starting at angle 0
+fn orbiting(planet: &Planet, radius: f64) -> Body {
+    body(
+        planet.position + Vec2::new(radius, 0.0),
+        Vec2::new(0.0, circular_speed(planet, radius)),
+    )
+}
+
+fn circular_speed(planet: &Planet, radius: f64) -> f64 {
+    (GRAVITATIONAL_CONSTANT * planet.mass / radius).sqrt()
+}
+
+/// Returns the state of the body after performing all burns
+fn follow(body: &Body, planet: &Planet, burns: &[Burn], now_s: f64) -> Body {
+    let planets = [*planet];
+
+    let mut body  = *body;
+    let mut now_s = now_s;
+
+    for burn in burns {
+        body = predict(&body, &planets, burn.time_s - now_s).unwrap();
+        body.velocity = body.velocity + burn.delta_v;
+        now_s = burn.time_s;
+    }
+
+    body
+}
+
+/// Returns the state of the body at `end_s`, after executing the maneuvers
+fn execute(
+    body      : &Body,
+    planets   : &[Planet],
+    maneuvers : &[ManeuverData],
+    max_thrust: f64,
+    now_s     : f64,
+    end_s     : f64,
+) -> Body {
+    let mut body   = *body;
+    let mut time_s = now_s;
+
+    while time_s < end_s {
+        let delta_t_s = PREDICTION_STEP_S.min(end_s - time_s);
+
+        for planet in planets {
+            apply_gravity(planet, &mut body);
+        }
+        for maneuver in maneuvers {
+            // Maneuvers don't necessarily start or end with a step.
+            let active_s = (time_s + delta_t_s).min(maneuver.end_s())
+                - time_s.max(maneuver.start_s);
+
+            if active_s > 0.0 {
+                let direction =
+                    Vec2::new(maneuver.angle.cos(), maneuver.angle.sin());
+                body.force = body.force
+                    + direction * max_thrust * maneuver.thrust
+                        * active_s / delta_t_s;
+            }
+        }
+        integrate(&mut body, delta_t_s);
+
+        time_s += delta_t_s;
+    }
+
+    body
+}